@@ -0,0 +1,249 @@
+//! Iteration over a struct with 200 fields by position compared with lookups by name,
+//! which `field_iter` did before. Run with `cargo bench`
+
+use std::time::{Duration, Instant};
+
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess, Default)]
+struct Wide {
+    field0: u64,
+    field1: String,
+    field2: u64,
+    field3: String,
+    field4: u64,
+    field5: String,
+    field6: u64,
+    field7: String,
+    field8: u64,
+    field9: String,
+    field10: u64,
+    field11: String,
+    field12: u64,
+    field13: String,
+    field14: u64,
+    field15: String,
+    field16: u64,
+    field17: String,
+    field18: u64,
+    field19: String,
+    field20: u64,
+    field21: String,
+    field22: u64,
+    field23: String,
+    field24: u64,
+    field25: String,
+    field26: u64,
+    field27: String,
+    field28: u64,
+    field29: String,
+    field30: u64,
+    field31: String,
+    field32: u64,
+    field33: String,
+    field34: u64,
+    field35: String,
+    field36: u64,
+    field37: String,
+    field38: u64,
+    field39: String,
+    field40: u64,
+    field41: String,
+    field42: u64,
+    field43: String,
+    field44: u64,
+    field45: String,
+    field46: u64,
+    field47: String,
+    field48: u64,
+    field49: String,
+    field50: u64,
+    field51: String,
+    field52: u64,
+    field53: String,
+    field54: u64,
+    field55: String,
+    field56: u64,
+    field57: String,
+    field58: u64,
+    field59: String,
+    field60: u64,
+    field61: String,
+    field62: u64,
+    field63: String,
+    field64: u64,
+    field65: String,
+    field66: u64,
+    field67: String,
+    field68: u64,
+    field69: String,
+    field70: u64,
+    field71: String,
+    field72: u64,
+    field73: String,
+    field74: u64,
+    field75: String,
+    field76: u64,
+    field77: String,
+    field78: u64,
+    field79: String,
+    field80: u64,
+    field81: String,
+    field82: u64,
+    field83: String,
+    field84: u64,
+    field85: String,
+    field86: u64,
+    field87: String,
+    field88: u64,
+    field89: String,
+    field90: u64,
+    field91: String,
+    field92: u64,
+    field93: String,
+    field94: u64,
+    field95: String,
+    field96: u64,
+    field97: String,
+    field98: u64,
+    field99: String,
+    field100: u64,
+    field101: String,
+    field102: u64,
+    field103: String,
+    field104: u64,
+    field105: String,
+    field106: u64,
+    field107: String,
+    field108: u64,
+    field109: String,
+    field110: u64,
+    field111: String,
+    field112: u64,
+    field113: String,
+    field114: u64,
+    field115: String,
+    field116: u64,
+    field117: String,
+    field118: u64,
+    field119: String,
+    field120: u64,
+    field121: String,
+    field122: u64,
+    field123: String,
+    field124: u64,
+    field125: String,
+    field126: u64,
+    field127: String,
+    field128: u64,
+    field129: String,
+    field130: u64,
+    field131: String,
+    field132: u64,
+    field133: String,
+    field134: u64,
+    field135: String,
+    field136: u64,
+    field137: String,
+    field138: u64,
+    field139: String,
+    field140: u64,
+    field141: String,
+    field142: u64,
+    field143: String,
+    field144: u64,
+    field145: String,
+    field146: u64,
+    field147: String,
+    field148: u64,
+    field149: String,
+    field150: u64,
+    field151: String,
+    field152: u64,
+    field153: String,
+    field154: u64,
+    field155: String,
+    field156: u64,
+    field157: String,
+    field158: u64,
+    field159: String,
+    field160: u64,
+    field161: String,
+    field162: u64,
+    field163: String,
+    field164: u64,
+    field165: String,
+    field166: u64,
+    field167: String,
+    field168: u64,
+    field169: String,
+    field170: u64,
+    field171: String,
+    field172: u64,
+    field173: String,
+    field174: u64,
+    field175: String,
+    field176: u64,
+    field177: String,
+    field178: u64,
+    field179: String,
+    field180: u64,
+    field181: String,
+    field182: u64,
+    field183: String,
+    field184: u64,
+    field185: String,
+    field186: u64,
+    field187: String,
+    field188: u64,
+    field189: String,
+    field190: u64,
+    field191: String,
+    field192: u64,
+    field193: String,
+    field194: u64,
+    field195: String,
+    field196: u64,
+    field197: String,
+    field198: u64,
+    field199: String,
+}
+
+const ROUNDS: u32 = 10_000;
+
+fn measure(name: &str, mut pass: impl FnMut() -> usize) -> Duration {
+    let start = Instant::now();
+    let total = (0..ROUNDS).map(|_| pass()).sum::<usize>();
+    let elapsed = start.elapsed();
+    println!(
+        "{:<10} {:>10.2?} per pass, {} fields matched",
+        name,
+        elapsed / ROUNDS,
+        total
+    );
+    elapsed
+}
+
+fn main() {
+    // Value unknown at compile time keeps passes from being optimized away
+    let wide = Wide {
+        field0: std::env::args().count() as u64,
+        ..Default::default()
+    };
+    let by_name = measure("by name", || {
+        Wide::FIELDS
+            .iter()
+            .filter_map(|name| wide.field(name))
+            .filter(|field| matches!(field, WideField::U64(_)))
+            .count()
+    });
+    let by_index = measure("by index", || {
+        wide.field_iter()
+            .filter(|(_, field)| matches!(field, WideField::U64(_)))
+            .count()
+    });
+    println!(
+        "field_iter is {:.1}x faster",
+        by_name.as_secs_f64() / by_index.as_secs_f64()
+    );
+}
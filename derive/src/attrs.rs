@@ -0,0 +1,590 @@
+use proc_macro2::Ident;
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Attribute, Expr, ExprLit, Lit, LitStr, Meta, MetaNameValue, Path, Token, Visibility,
+};
+
+/// List of derive macroses from `derive = [Debug, Clone]`
+pub(crate) type DeriveList = Punctuated<Path, Token![,]>;
+
+const CONTAINER_OPTIONS: &[&str] = &[
+    "name",
+    "derive",
+    "derive_mut",
+    "derive_value",
+    "derive_all",
+    "parse",
+    "fields_vis",
+    "rename_all",
+    "lookup",
+    "variants",
+    "markers",
+    "into_fields",
+];
+const FIELD_OPTIONS: &[&str] = &[
+    "name",
+    "rename",
+    "alias",
+    "no_parse",
+    "parse_with",
+    "skip",
+    "skip_mut",
+    "skip_iter",
+    "nested",
+    "flatten",
+];
+
+/// Options collected from all `#[fieldname_enum(...)]` attributes of the container
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    pub name: Option<Ident>,
+    pub derive: Option<DeriveList>,
+    pub derive_mut: Option<DeriveList>,
+    pub derive_value: Option<DeriveList>,
+    /// Generate `set_field_str` with `#[fieldname_enum(parse)]`
+    pub parse: bool,
+    /// Visibility of `FIELDS` and `FIELD_COUNT` set with `#[fieldname_enum(fields_vis = "pub")]`
+    pub fields_vis: Option<Visibility>,
+    /// Naming convention of field names set with `#[fieldname_enum(rename_all = "camelCase")]`
+    pub rename_all: Option<RenameRule>,
+    /// How lookups by string match field names, `#[fieldname_enum(lookup = "normalized")]`
+    pub lookup: LookupMode,
+    /// What variants of generated enums stand for, `#[fieldname_enum(variants = "per_field")]`
+    pub variants: VariantMode,
+    /// Generate marker types of fields with `#[fieldname_enum(markers)]`
+    pub markers: Option<Markers>,
+    /// Generate consuming `into_field` and `into_fields` with `#[fieldname_enum(into_fields)]`
+    pub into_fields: bool,
+}
+
+/// Marker types requested with `#[fieldname_enum(markers)]` or `#[fieldname_enum(markers = "user_markers")]`
+pub(crate) struct Markers {
+    pub key: Ident,
+    /// Name of the module with markers instead of `<struct>_fields`
+    pub module: Option<Ident>,
+}
+
+struct ContainerOption {
+    key: Ident,
+    kind: ContainerOptionKind,
+}
+
+enum ContainerOptionKind {
+    Name(Ident),
+    Derive(DeriveList),
+    DeriveMut(DeriveList),
+    DeriveValue(DeriveList),
+    DeriveAll(DeriveList),
+    Parse,
+    FieldsVis(Visibility),
+    RenameAll(RenameRule),
+    Lookup(LookupMode),
+    Variants(VariantMode),
+    Markers(Option<Ident>),
+    IntoFields,
+}
+
+impl Parse for ContainerOption {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = parse_option_key(input, "fieldname_enum", CONTAINER_OPTIONS)?;
+
+        let kind = match key.to_string().as_str() {
+            "name" => ContainerOptionKind::Name(parse_lit_ident(&parse_value(input)?)?),
+            "derive" => ContainerOptionKind::Derive(parse_derive_list(input, &key)?),
+            "derive_mut" => ContainerOptionKind::DeriveMut(parse_derive_list(input, &key)?),
+            "derive_value" => ContainerOptionKind::DeriveValue(parse_derive_list(input, &key)?),
+            "derive_all" => ContainerOptionKind::DeriveAll(parse_derive_list(input, &key)?),
+            "parse" => ContainerOptionKind::Parse,
+            "fields_vis" => ContainerOptionKind::FieldsVis(parse_lit_vis(&parse_value(input)?)?),
+            "rename_all" => {
+                ContainerOptionKind::RenameAll(RenameRule::from_lit(&parse_value(input)?)?)
+            }
+            "lookup" => ContainerOptionKind::Lookup(LookupMode::from_lit(&parse_value(input)?)?),
+            "variants" => {
+                ContainerOptionKind::Variants(VariantMode::from_lit(&parse_value(input)?)?)
+            }
+            "markers" if input.peek(Token![=]) => {
+                let lit = parse_value(input)?;
+                let mut module = parse_lit_ident(&lit)?;
+                module.set_span(lit.span());
+                ContainerOptionKind::Markers(Some(module))
+            }
+            "markers" => ContainerOptionKind::Markers(None),
+            "into_fields" => ContainerOptionKind::IntoFields,
+            _ => unreachable!("`parse_option_key` accepts only keys of `CONTAINER_OPTIONS`"),
+        };
+        Ok(ContainerOption { key, kind })
+    }
+}
+
+impl ContainerAttrs {
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container = ContainerAttrs::default();
+        let mut derive_all: Option<(Ident, DeriveList)> = None;
+        let mut seen: Vec<Ident> = Vec::new();
+
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("fieldname_enum"))
+        {
+            let options =
+                attr.parse_args_with(Punctuated::<ContainerOption, Token![,]>::parse_terminated)?;
+            for ContainerOption { key, kind } in options {
+                check_duplicate(&mut seen, &key, "fieldname_enum")?;
+
+                match kind {
+                    ContainerOptionKind::Name(name) => container.name = Some(name),
+                    ContainerOptionKind::Derive(list) => container.derive = Some(list),
+                    ContainerOptionKind::DeriveMut(list) => container.derive_mut = Some(list),
+                    ContainerOptionKind::DeriveValue(list) => container.derive_value = Some(list),
+                    ContainerOptionKind::DeriveAll(list) => derive_all = Some((key, list)),
+                    ContainerOptionKind::Parse => container.parse = true,
+                    ContainerOptionKind::FieldsVis(vis) => container.fields_vis = Some(vis),
+                    ContainerOptionKind::RenameAll(rule) => container.rename_all = Some(rule),
+                    ContainerOptionKind::Lookup(mode) => container.lookup = mode,
+                    ContainerOptionKind::Variants(mode) => container.variants = mode,
+                    ContainerOptionKind::Markers(module) => {
+                        container.markers = Some(Markers { key, module })
+                    }
+                    ContainerOptionKind::IntoFields => container.into_fields = true,
+                }
+            }
+        }
+
+        if let Some((key, list)) = derive_all {
+            if let Some(conflict) = seen
+                .iter()
+                .find(|seen| *seen == "derive" || *seen == "derive_mut" || *seen == "derive_value")
+            {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("`derive_all` cannot be combined with `{}`", conflict),
+                ));
+            }
+            container.derive = Some(list.clone());
+            container.derive_mut = Some(list.clone());
+            container.derive_value = Some(list);
+        }
+
+        Ok(container)
+    }
+}
+
+/// Naming conventions of `rename_all` mirroring serde ones
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+const RENAME_RULES: &[(&str, RenameRule)] = &[
+    ("lowercase", RenameRule::Lower),
+    ("UPPERCASE", RenameRule::Upper),
+    ("PascalCase", RenameRule::Pascal),
+    ("camelCase", RenameRule::Camel),
+    ("snake_case", RenameRule::Snake),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+    ("kebab-case", RenameRule::Kebab),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+];
+
+impl RenameRule {
+    fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+        RENAME_RULES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let expected = RENAME_RULES
+                    .iter()
+                    .map(|(name, _)| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "Unknown `rename_all` value `{}`, expected one of {}",
+                        value, expected
+                    ),
+                )
+            })
+    }
+
+    /// Applies the convention to `snake_case` field name
+    pub fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                })
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// Modes of `lookup` option
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum LookupMode {
+    /// Only exact names match
+    #[default]
+    Exact,
+    /// Names match ignoring ASCII case
+    AsciiCaseInsensitive,
+    /// Names match ignoring ASCII case, `_` and `-`
+    Normalized,
+}
+
+const LOOKUP_MODES: &[(&str, LookupMode)] = &[
+    ("exact", LookupMode::Exact),
+    ("ascii_case_insensitive", LookupMode::AsciiCaseInsensitive),
+    ("normalized", LookupMode::Normalized),
+];
+
+impl LookupMode {
+    fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+        LOOKUP_MODES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, mode)| *mode)
+            .ok_or_else(|| {
+                let expected = LOOKUP_MODES
+                    .iter()
+                    .map(|(name, _)| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "Unknown `lookup` value `{}`, expected one of {}",
+                        value, expected
+                    ),
+                )
+            })
+    }
+
+    /// Name of the mode in the attribute
+    pub fn name(self) -> &'static str {
+        LOOKUP_MODES
+            .iter()
+            .find(|(_, mode)| *mode == self)
+            .map_or("exact", |(name, _)| name)
+    }
+
+    /// Form of the name compared by the lookup
+    pub fn normalize(self, name: &str) -> String {
+        match self {
+            LookupMode::Exact => name.to_owned(),
+            LookupMode::AsciiCaseInsensitive => name.to_ascii_lowercase(),
+            LookupMode::Normalized => name
+                .chars()
+                .filter(|c| *c != '_' && *c != '-')
+                .map(|c| c.to_ascii_lowercase())
+                .collect(),
+        }
+    }
+}
+
+/// Modes of `variants` option
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum VariantMode {
+    /// Variants are named after field types and shared by fields of the same type
+    #[default]
+    PerType,
+    /// Every field gets its own variant named after the field
+    PerField,
+}
+
+const VARIANT_MODES: &[(&str, VariantMode)] = &[
+    ("per_type", VariantMode::PerType),
+    ("per_field", VariantMode::PerField),
+];
+
+impl VariantMode {
+    fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        let value = lit.value();
+        VARIANT_MODES
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, mode)| *mode)
+            .ok_or_else(|| {
+                let expected = VARIANT_MODES
+                    .iter()
+                    .map(|(name, _)| format!("`{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "Unknown `variants` value `{}`, expected one of {}",
+                        value, expected
+                    ),
+                )
+            })
+    }
+}
+
+fn parse_derive_list(input: ParseStream, key: &Ident) -> syn::Result<DeriveList> {
+    input.parse::<Token![=]>()?;
+    if !input.peek(token::Bracket) {
+        return Err(input.error(format!(
+            "`{}` expects a list of derives like `[Debug, Clone]`",
+            key
+        )));
+    }
+    let content;
+    bracketed!(content in input);
+    content.parse_terminated(Path::parse_mod_style, Token![,])
+}
+
+/// Value of `key = value` option
+fn parse_value<T: Parse>(input: ParseStream) -> syn::Result<T> {
+    input.parse::<Token![=]>()?;
+    input.parse()
+}
+
+fn parse_option_key(input: ParseStream, attr_name: &str, options: &[&str]) -> syn::Result<Ident> {
+    let key: Ident = input.parse()?;
+    if !options.iter().any(|option| key == option) {
+        let expected = options
+            .iter()
+            .map(|option| format!("`{}`", option))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(syn::Error::new(
+            key.span(),
+            format!(
+                "Unknown `{}` option `{}`, expected one of {}",
+                attr_name, key, expected
+            ),
+        ));
+    }
+    Ok(key)
+}
+
+fn check_duplicate(seen: &mut Vec<Ident>, key: &Ident, attr_name: &str) -> syn::Result<()> {
+    if seen.contains(key) {
+        return Err(syn::Error::new(
+            key.span(),
+            format!("Duplicate `{}` option `{}`", attr_name, key),
+        ));
+    }
+    seen.push(key.clone());
+    Ok(())
+}
+
+/// Options collected from `#[fieldname = "..."]` and `#[fieldname(...)]` attributes of the field
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// Variant name set with `#[fieldname = "VariantName"]`
+    pub variant: Option<Ident>,
+    /// Name of tuple struct field set with `#[fieldname(name = "r")]`
+    pub name: Option<LitStr>,
+    /// Name used for runtime access set with `#[fieldname(rename = "dateOfBirth")]`
+    pub rename: Option<LitStr>,
+    /// Deprecated names also accepted by lookups, `#[fieldname(alias = "old_name")]`
+    pub aliases: Vec<LitStr>,
+    /// Field excluded from `set_field_str` with `#[fieldname(no_parse)]`
+    pub no_parse: Option<Ident>,
+    /// Parser used by `set_field_str` instead of `FromStr`, `#[fieldname(parse_with = "path::to::fn")]`
+    pub parse_with: Option<Path>,
+    /// Field excluded from all generated items with `#[fieldname(skip)]`
+    pub skip: bool,
+    /// Field excluded from mutable access with `#[fieldname(skip_mut)]`
+    pub skip_mut: bool,
+    /// Field excluded from `field_iter` with `#[fieldname(skip_iter)]`
+    pub skip_iter: bool,
+    /// Field of type deriving `FieldnameAccess` accessible by path, `#[fieldname(nested)]`
+    pub nested: bool,
+    /// Field of type deriving `FieldnameAccess` which fields are accessible as fields of
+    /// the struct, `#[fieldname(flatten)]`
+    pub flatten: bool,
+}
+
+struct FieldOption {
+    key: Ident,
+    kind: FieldOptionKind,
+}
+
+enum FieldOptionKind {
+    Name(LitStr),
+    Rename(LitStr),
+    Alias(LitStr),
+    NoParse,
+    ParseWith(Path),
+    Skip,
+    SkipMut,
+    SkipIter,
+    Nested,
+    Flatten,
+}
+
+impl Parse for FieldOption {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = parse_option_key(input, "fieldname", FIELD_OPTIONS)?;
+
+        let kind = match key.to_string().as_str() {
+            "name" => FieldOptionKind::Name(parse_value(input)?),
+            "rename" => FieldOptionKind::Rename(parse_value(input)?),
+            "alias" => FieldOptionKind::Alias(parse_value(input)?),
+            "no_parse" => FieldOptionKind::NoParse,
+            "parse_with" => FieldOptionKind::ParseWith(parse_value::<LitStr>(input)?.parse()?),
+            "skip" => FieldOptionKind::Skip,
+            "skip_mut" => FieldOptionKind::SkipMut,
+            "skip_iter" => FieldOptionKind::SkipIter,
+            "nested" => FieldOptionKind::Nested,
+            "flatten" => FieldOptionKind::Flatten,
+            _ => unreachable!("`parse_option_key` accepts only keys of `FIELD_OPTIONS`"),
+        };
+        Ok(FieldOption { key, kind })
+    }
+}
+
+impl FieldAttrs {
+    pub fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut field = FieldAttrs::default();
+        let mut seen: Vec<Ident> = Vec::new();
+        let mut variant_attr = None;
+
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("fieldname"))
+        {
+            match &attr.meta {
+                Meta::NameValue(MetaNameValue {
+                    value:
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(str), ..
+                        }),
+                    ..
+                }) => {
+                    if field.variant.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "Duplicate `#[fieldname = \"...\"]` attribute",
+                        ));
+                    }
+                    field.variant = Some(parse_lit_ident(str)?);
+                    variant_attr = Some(attr);
+                }
+                Meta::List(list) => {
+                    let options = list
+                        .parse_args_with(Punctuated::<FieldOption, Token![,]>::parse_terminated)?;
+                    for FieldOption { key, kind } in options {
+                        if key == "alias" {
+                            seen.push(key.clone());
+                        } else {
+                            check_duplicate(&mut seen, &key, "fieldname")?;
+                        }
+                        match kind {
+                            FieldOptionKind::Name(name) => field.name = Some(name),
+                            FieldOptionKind::Rename(name) => field.rename = Some(name),
+                            FieldOptionKind::Alias(alias) => field.aliases.push(alias),
+                            FieldOptionKind::NoParse => field.no_parse = Some(key),
+                            FieldOptionKind::ParseWith(path) => field.parse_with = Some(path),
+                            FieldOptionKind::Skip => field.skip = true,
+                            FieldOptionKind::SkipMut => field.skip_mut = true,
+                            FieldOptionKind::SkipIter => field.skip_iter = true,
+                            FieldOptionKind::Nested => field.nested = true,
+                            FieldOptionKind::Flatten => field.flatten = true,
+                        }
+                    }
+                }
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "Expected `#[fieldname = \"VariantName\"]` or `#[fieldname(...)]`",
+                    ))
+                }
+            }
+        }
+
+        if field.skip {
+            if let Some(conflict) = seen.iter().find(|seen| *seen != "skip") {
+                return Err(syn::Error::new(
+                    conflict.span(),
+                    format!("`skip` cannot be combined with `{}`", conflict),
+                ));
+            }
+            if let Some(attr) = variant_attr {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`skip` cannot be combined with `#[fieldname = \"...\"]`",
+                ));
+            }
+        }
+        if field.flatten {
+            if let Some(conflict) = seen
+                .iter()
+                .find(|seen| *seen != "flatten" && *seen != "skip_mut" && *seen != "skip_iter")
+            {
+                return Err(syn::Error::new(
+                    conflict.span(),
+                    format!("`flatten` cannot be combined with `{}`", conflict),
+                ));
+            }
+        }
+        if let (Some(_), Some(rename)) = (&field.name, &field.rename) {
+            return Err(syn::Error::new(
+                rename.span(),
+                "`rename` cannot be combined with `name`",
+            ));
+        }
+        if let (Some(key), Some(_)) = (&field.no_parse, &field.parse_with) {
+            return Err(syn::Error::new(
+                key.span(),
+                "`no_parse` cannot be combined with `parse_with`",
+            ));
+        }
+
+        Ok(field)
+    }
+}
+
+fn parse_lit_ident(lit: &LitStr) -> syn::Result<Ident> {
+    syn::parse_str(&lit.value()).map_err(|_| {
+        syn::Error::new(
+            lit.span(),
+            format!("`{}` is not a valid identifier", lit.value()),
+        )
+    })
+}
+
+fn parse_lit_vis(lit: &LitStr) -> syn::Result<Visibility> {
+    syn::parse_str(&lit.value()).map_err(|_| {
+        syn::Error::new(
+            lit.span(),
+            format!("`{}` is not a valid visibility", lit.value()),
+        )
+    })
+}
@@ -0,0 +1,2777 @@
+mod attrs;
+
+use attrs::{ContainerAttrs, FieldAttrs, LookupMode, VariantMode};
+use itertools::Itertools;
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Fields,
+    Generics, Index, Member, Type, Visibility,
+};
+
+/// Derive macro for safe struct field access by their names in runtime, see `fieldname_access`
+/// crate for the documentation
+#[proc_macro_derive(FieldnameAccess, attributes(fieldname_enum, fieldname))]
+pub fn fieldname_accessor(inp: TokenStream) -> TokenStream {
+    let inp = parse_macro_input!(inp as DeriveInput);
+    expand(inp)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(inp: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        ident: struct_ident,
+        vis: visibility,
+        generics,
+        ..
+    } = &inp;
+    let container = ContainerAttrs::from_attrs(&inp.attrs)?;
+
+    let mut variants = match &inp.data {
+        Data::Struct(structure) => {
+            if let Fields::Unit = structure.fields {
+                return Err(syn::Error::new_spanned(
+                    struct_ident,
+                    "FieldnameAccess cannot be used with unit structs",
+                ));
+            }
+            let (fields, flattened) = collect_fields(&structure.fields, &container)?;
+            vec![VariantInfo {
+                ident: None,
+                path: quote!(Self),
+                fields,
+                flattened,
+            }]
+        }
+        Data::Enum(_) if container.markers.is_some() => {
+            let key = container.markers.as_ref().map(|markers| &markers.key);
+            return Err(syn::Error::new_spanned(
+                key,
+                "`markers` is only supported on structs",
+            ));
+        }
+        Data::Enum(enumeration) => enumeration
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                let (fields, flattened) = collect_fields(&variant.fields, &container)?;
+                if let Some(flatten) = flattened.first() {
+                    return Err(syn::Error::new(
+                        flatten.span,
+                        "`flatten` is only supported on struct fields",
+                    ));
+                }
+                Ok(VariantInfo {
+                    ident: Some(ident.clone()),
+                    path: quote!(Self::#ident),
+                    fields,
+                    flattened,
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()?,
+        Data::Union(union) => {
+            return Err(syn::Error::new(
+                union.union_token.span,
+                "FieldnameAccess cannot be used with unions",
+            ))
+        }
+    };
+    disambiguate_variants(&mut variants)?;
+    check_variant_collisions(&variants)?;
+    let is_enum = matches!(inp.data, Data::Enum(_));
+
+    let field_lifetime: syn::GenericParam = parse_quote!('field);
+    let (impl_generics, ty_generics, where_clauses) = generics.split_for_impl();
+
+    let mut enum_generics = generics.clone();
+    enum_generics.params.push(field_lifetime.clone());
+    let (_, enum_ty_generics, _) = enum_generics.split_for_impl();
+
+    let field_map = variants
+        .iter()
+        .flat_map(|variant| &variant.fields)
+        .collect::<Vec<_>>();
+    if field_map.is_empty() {
+        return Err(syn::Error::new_spanned(
+            struct_ident,
+            "FieldnameAccess requires at least one field that is not skipped",
+        ));
+    }
+    let flattened = variants
+        .iter()
+        .flat_map(|variant| &variant.flattened)
+        .collect::<Vec<_>>();
+    let generic_names = generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .chain(generics.const_params().map(|param| param.ident.to_string()))
+        .collect::<Vec<_>>();
+    for (idx, flatten) in flattened.iter().enumerate() {
+        if mentions_idents(flatten.ty.to_token_stream(), &generic_names) {
+            return Err(syn::Error::new_spanned(
+                &flatten.ty,
+                "Type of `flatten` field cannot depend on generic parameters",
+            ));
+        }
+        let clash = field_map
+            .iter()
+            .any(|field| field.variant == flatten.variant)
+            || flattened[..idx]
+                .iter()
+                .any(|other| other.variant == flatten.variant);
+        if clash {
+            return Err(syn::Error::new(
+                flatten.span,
+                format!(
+                    "Variant `{}` of flattened field is already used, specify another one with `#[fieldname = \"...\"]`",
+                    flatten.variant
+                ),
+            ));
+        }
+    }
+    let flatten_tys = flattened
+        .iter()
+        .map(|flatten| &flatten.ty)
+        .collect::<Vec<_>>();
+    let flatten_patterns = flattened
+        .iter()
+        .map(|FlattenInfo { member, .. }| quote!(Self { #member: field, .. }))
+        .collect::<Vec<_>>();
+    let flatten_variants = flattened
+        .iter()
+        .map(|flatten| &flatten.variant)
+        .collect::<Vec<_>>();
+    let mut_flattened = flattened
+        .iter()
+        .filter(|flatten| flatten.mutable)
+        .collect::<Vec<_>>();
+    let mut_flatten_tys = mut_flattened.iter().map(|flatten| &flatten.ty);
+    let mut_flatten_patterns = mut_flattened
+        .iter()
+        .map(|FlattenInfo { member, .. }| quote!(Self { #member: field, .. }));
+    let mut_flatten_variants = mut_flattened.iter().map(|flatten| &flatten.variant);
+
+    let alias_map = collect_aliases(&field_map)?;
+    let fuzzy_lookup = generate_fuzzy_lookup(container.lookup, &field_map, &flatten_tys)?;
+    let alias_entries = alias_map
+        .iter()
+        .map(|(alias, name)| quote!((#alias, #name)))
+        .collect::<Vec<_>>();
+    let (aliases_const, aliases_len) = if flattened.is_empty() {
+        let aliases_len = alias_entries.len();
+        (quote!([#(#alias_entries),*]), quote!(#aliases_len))
+    } else {
+        generate_flatten_aliases(&alias_entries, &flatten_tys)
+    };
+    let mut_field_map = field_map
+        .iter()
+        .copied()
+        .filter(|field| field.mutable)
+        .collect::<Vec<_>>();
+    let field_list = field_map
+        .iter()
+        .map(|field| &field.name)
+        .unique()
+        .collect::<Vec<_>>();
+    let name_count = field_list.len();
+    let fields_vis = container.fields_vis.unwrap_or_else(|| visibility.clone());
+    let (fields_const, fields_len, iter_fields_const, flatten_check) = if flattened.is_empty() {
+        let iter_fields_const = (!is_enum).then(|| {
+            let iter_names = field_map
+                .iter()
+                .filter(|field| field.iterable)
+                .map(|field| &field.name)
+                .collect::<Vec<_>>();
+            let iter_len = iter_names.len();
+            quote! {
+                /// List with fields yielded by `field_iter`
+                #fields_vis const ITER_FIELDS: [&'static str; #iter_len] = [#(#iter_names),*];
+            }
+        });
+        (
+            quote!([#(#field_list),*]),
+            quote!(#name_count),
+            iter_fields_const,
+            None,
+        )
+    } else {
+        let fields = &variants[0].fields;
+        let flattened = &variants[0].flattened;
+        let (fields_const, fields_len) =
+            generate_flatten_list(fields, flattened, &format_ident!("FIELDS"), false);
+        let (iter_fields_builder, iter_len) =
+            generate_flatten_list(fields, flattened, &format_ident!("ITER_FIELDS"), true);
+        let iter_fields_const = quote! {
+            /// List with fields yielded by `field_iter`
+            #fields_vis const ITER_FIELDS: [&'static str; #iter_len] = #iter_fields_builder;
+        };
+        let flatten_check =
+            generate_flatten_check(struct_ident, fields, flattened, container.lookup);
+        (
+            fields_const,
+            fields_len,
+            Some(iter_fields_const),
+            Some(flatten_check),
+        )
+    };
+    let (alias_names, alias_canonicals): (Vec<_>, Vec<_>) = alias_map.into_iter().unzip();
+    let is_alias = {
+        let own = (!alias_names.is_empty()).then(|| quote!(matches!(fieldname, #(#alias_names)|*)));
+        // Alias is matched by lookup when it's a key which is not a canonical name
+        let fuzzy = fuzzy_lookup
+            .as_ref()
+            .map(|fuzzy_lookup| quote!(matches!(#fuzzy_lookup, Some((key, name)) if key != name)));
+        let flattened = flatten_tys
+            .iter()
+            .map(|ty| quote!(<#ty>::is_deprecated_alias(fieldname)));
+        let mut checks = own.into_iter().chain(fuzzy).chain(flattened);
+        match checks.next() {
+            Some(first) => quote!(#first #(|| #checks)*),
+            None => quote! {
+                let _ = fieldname;
+                false
+            },
+        }
+    };
+
+    let derive = container.derive.map(|derives| quote!(#[derive(#derives)]));
+    let derive_mut = container
+        .derive_mut
+        .map(|derives| quote!(#[derive(#derives)]));
+    let derive_value = container
+        .derive_value
+        .map(|derives| quote!(#[derive(#derives)]));
+
+    let value_enum_ident = container.name.unwrap_or(Ident::new(
+        &format!("{}Field", struct_ident),
+        Span::call_site(),
+    ));
+    let value_enum_ident_mut = Ident::new(&format!("{}Mut", value_enum_ident), Span::call_site());
+    let value_enum_ident_owned =
+        Ident::new(&format!("{}Value", value_enum_ident), Span::call_site());
+    let iter_ident = Ident::new(&format!("{}FieldIter", value_enum_ident), Span::call_site());
+
+    let mut value_variants = generate_enum_variants(&field_map, &quote!(&'field));
+    let mut value_variants_mut = generate_enum_variants(&mut_field_map, &quote!(&'field mut));
+    for flatten in &flattened {
+        let variant = &flatten.variant;
+        let ty = generate_derived_type(&flatten.ty, "Field", &field_lifetime, "flatten")?;
+        value_variants.push(quote!(#variant(#ty)));
+        if flatten.mutable {
+            let ty = generate_derived_type(&flatten.ty, "FieldMut", &field_lifetime, "flatten")?;
+            value_variants_mut.push(quote!(#variant(#ty)));
+        }
+    }
+    let mut value_variants_owned = generate_enum_variants(&field_map, &quote!());
+    for flatten in &flattened {
+        let variant = &flatten.variant;
+        let (path, args) = derived_type_path(&flatten.ty, "FieldValue", "flatten")?;
+        value_variants_owned.push(quote!(#variant(#path<#(#args),*>)));
+    }
+    for variants in [&mut value_variants, &mut value_variants_mut] {
+        let unused_variant = generate_unused_variant(&enum_generics, variants);
+        variants.extend(unused_variant);
+    }
+    let unused_variant_arm =
+        generate_unused_variant(generics, &value_variants_owned).map(|unused_variant| {
+            value_variants_owned.push(unused_variant);
+            quote!(Self::__Unused(never, ..) => match never {},)
+        });
+
+    let field_patterns = generate_field_patterns(&variants);
+    let field_refs = generate_field_refs(&field_map, &value_enum_ident);
+    let field_refs_mut = generate_field_refs(&mut_field_map, &value_enum_ident_mut);
+    let field_values = generate_field_refs(&field_map, &value_enum_ident_owned);
+    let into_fields_arms = generate_into_fields_arms(&variants, &value_enum_ident_owned);
+    let iter_mut_arms = generate_iter_mut_arms(&variants, &value_enum_ident_mut);
+
+    let set_error_ident = quote!(::fieldname_access::SetFieldError);
+    let set_field_arms =
+        generate_set_field_arms(&variants, &value_enum_ident_owned, &set_error_ident);
+    let parse_impl = if container.parse {
+        let parse_error_ident = quote!(::fieldname_access::ParseFieldError);
+        let set_field_str_arms = generate_set_field_str_arms(&variants, &parse_error_ident);
+        let unknown_field = quote!(Err(#parse_error_ident::UnknownField(fieldname.to_string())));
+        let set_field_str_default = if flatten_tys.is_empty() {
+            unknown_field
+        } else {
+            // Value enums of flattened fields are unknown, so they cannot be set from string
+            let first = flatten_tys[0];
+            let rest = &flatten_tys[1..];
+            quote! {
+                match <#first>::canonical_name(fieldname)#(.or_else(|| <#rest>::canonical_name(fieldname)))* {
+                    Some(name) => Err(#parse_error_ident::NotParsable(name)),
+                    None => #unknown_field,
+                }
+            }
+        };
+        let set_field_str_fallback = generate_lookup_fallback(
+            &fuzzy_lookup,
+            quote!(set_field_str(name, raw)),
+            None,
+            set_field_str_default,
+        );
+        let parse_bounds = field_map
+            .iter()
+            .filter(|field| matches!(field.parse, FieldParse::FromStr))
+            .map(|FieldInfo { ty, .. }| {
+                quote_spanned! {ty.span()=>
+                    #ty: ::std::str::FromStr,
+                    <#ty as ::std::str::FromStr>::Err: ::std::fmt::Display
+                }
+            });
+        quote! {
+            impl #impl_generics #struct_ident #ty_generics #where_clauses {
+                /// Method for setting field by its name from string using `FromStr` of the field type
+                #visibility fn set_field_str(&mut self, fieldname: &str, raw: &str) -> Result<(), #parse_error_ident>
+                where
+                    #(#parse_bounds,)*
+                {
+                    match (self, fieldname) {
+                        #(#set_field_str_arms,)*
+                        #set_field_str_fallback
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let name_enum_ident = format_ident!("{}FieldName", struct_ident);
+    let name_error_ident = quote!(::fieldname_access::FieldNameError);
+    let unknown_field_ident = quote!(::fieldname_access::UnknownField);
+    let struct_name = struct_ident.to_string();
+    let lookup_target = |mutable: bool| {
+        let targets = variants.iter().map(|variant| {
+            let type_name = variant.ident.as_ref().map_or_else(
+                || struct_name.clone(),
+                |ident| format!("{}::{}", struct_ident, ident),
+            );
+            if !variant.flattened.is_empty() {
+                return quote!((#type_name, &Self::FIELDS));
+            }
+            let valid = variant
+                .fields
+                .iter()
+                .filter(|field| field.mutable || !mutable)
+                .map(|field| &field.name);
+            quote!((#type_name, &[#(#valid),*]))
+        });
+        let variant_paths = variants.iter().map(|variant| &variant.path);
+        quote! {
+            match *self {
+                #(#variant_paths { .. } => #targets,)*
+            }
+        }
+    };
+    let (lookup_target, lookup_target_mut) = (lookup_target(false), lookup_target(true));
+    let canonical_fallback = {
+        let mut lookups = flatten_tys
+            .iter()
+            .map(|ty| quote!(<#ty>::canonical_name(fieldname)))
+            .chain(
+                fuzzy_lookup
+                    .iter()
+                    .map(|fuzzy_lookup| quote!(#fuzzy_lookup.map(|(_, name)| name))),
+            );
+        match lookups.next() {
+            Some(first) => quote!(#first #(.or_else(|| #lookups))*),
+            None => quote!(None),
+        }
+    };
+    let name_from_str_fallback = match &fuzzy_lookup {
+        Some(fuzzy_lookup) => quote! {
+            match #fuzzy_lookup {
+                // Fields of flattened fields have no variants and resolve into themselves
+                Some((_, name)) if name != fieldname => Self::from_str(name),
+                _ => Err(#name_error_ident::UnknownName(fieldname.to_string())),
+            }
+        },
+        None => quote!(Err(#name_error_ident::UnknownName(fieldname.to_string()))),
+    };
+    let flatten_delegate = (!flattened.is_empty()).then(|| {
+        quote! {
+            #(if <#flatten_tys>::canonical_name(fieldname).is_some() {
+                let #flatten_patterns = this;
+                return field.field(fieldname).map(#value_enum_ident::#flatten_variants);
+            })*
+        }
+    });
+    let flatten_delegate_mut = (!mut_flattened.is_empty()).then(|| {
+        quote! {
+            #(if <#mut_flatten_tys>::canonical_name(fieldname).is_some() {
+                let #mut_flatten_patterns = this;
+                return field.field_mut(fieldname).map(#value_enum_ident_mut::#mut_flatten_variants);
+            })*
+        }
+    });
+    let field_fallback = generate_lookup_fallback(
+        &fuzzy_lookup,
+        quote!(field(name)),
+        flatten_delegate,
+        quote!(None),
+    );
+    let field_mut_fallback = generate_lookup_fallback(
+        &fuzzy_lookup,
+        quote!(field_mut(name)),
+        flatten_delegate_mut,
+        quote!(None),
+    );
+    let flatten_delegate_owned = (!flattened.is_empty()).then(|| {
+        quote! {
+            #(if <#flatten_tys>::canonical_name(fieldname).is_some() {
+                let #flatten_patterns = this;
+                return field.into_field(fieldname).map(#value_enum_ident_owned::#flatten_variants);
+            })*
+        }
+    });
+    let flatten_delegate_set = (!flattened.is_empty()).then(|| {
+        let delegates = flattened.iter().map(|flatten| {
+            let FlattenInfo {
+                member,
+                ty,
+                variant,
+                ..
+            } = flatten;
+            if !flatten.mutable {
+                return quote! {
+                    if let Some(name) = <#ty>::canonical_name(fieldname) {
+                        return Err(#set_error_ident::ReadOnly(name));
+                    }
+                };
+            }
+            let expected = variant.to_string();
+            quote! {
+                if let Some(name) = <#ty>::canonical_name(fieldname) {
+                    let Self { #member: field, .. } = this;
+                    return match value {
+                        #value_enum_ident_owned::#variant(value) => field
+                            .set_field(fieldname, value)
+                            .map(#value_enum_ident_owned::#variant),
+                        value => Err(#set_error_ident::MismatchedValue {
+                            field: name,
+                            expected: #expected,
+                            found: value.variant_name(),
+                        }),
+                    };
+                }
+            }
+        });
+        quote!(#(#delegates)*)
+    });
+    let into_field_fallback = generate_lookup_fallback(
+        &fuzzy_lookup,
+        quote!(into_field(name)),
+        flatten_delegate_owned,
+        quote!(None),
+    );
+    let set_field_fallback = generate_lookup_fallback(
+        &fuzzy_lookup,
+        quote!(set_field(name, value)),
+        flatten_delegate_set,
+        quote!(Err(#set_error_ident::UnknownField(fieldname.to_string()))),
+    );
+    let field_name_variants = generate_name_variants(&field_map)?;
+    let markers = container.markers.as_ref().map(|markers| {
+        let module = markers.module.clone().unwrap_or_else(|| {
+            format_ident!("{}_fields", to_snake_case(&struct_ident.to_string()))
+        });
+        generate_markers(
+            visibility,
+            struct_ident,
+            generics,
+            &field_map,
+            &field_name_variants,
+            &module,
+        )
+    });
+    let name_variants = field_name_variants.iter().unique().collect::<Vec<_>>();
+    let path_enum_ident = format_ident!("{}FieldPath", struct_ident);
+    let path_enum_ident_mut = format_ident!("{}FieldPathMut", struct_ident);
+    let path_error_ident = quote!(::fieldname_access::FieldPathError);
+    let path_error_kind_ident = quote!(::fieldname_access::FieldPathErrorKind);
+    let mut path_variants = Vec::new();
+    let mut path_variants_mut = Vec::new();
+    let mut path_arms = Vec::new();
+    let mut path_arms_mut = Vec::new();
+    let mut field_path_pushes = Vec::new();
+    for (idx, ((field, pattern), variant)) in field_map
+        .iter()
+        .zip(&field_patterns)
+        .zip(&field_name_variants)
+        .enumerate()
+    {
+        let (steps, elem_ty) = collection_steps(&field.ty);
+        let first = !field_map[..idx]
+            .iter()
+            .any(|other| other.name == field.name);
+        let name = &field.name;
+        // Only structs have flattened fields, so `idx` is position of the field among them
+        for flatten in flattened.iter().filter(|flatten| flatten.position == idx) {
+            let ty = &flatten.ty;
+            field_path_pushes.push(quote!(paths.extend(<#ty>::field_paths());));
+        }
+        if first {
+            let index_marks = "[]".repeat(
+                steps
+                    .iter()
+                    .filter(|step| **step != PathStep::Optional)
+                    .count(),
+            );
+            field_path_pushes.push(if field.nested {
+                quote! {
+                    paths.extend(
+                        <#elem_ty>::field_paths()
+                            .into_iter()
+                            .map(|path| format!("{}{}.{}", #name, #index_marks, path)),
+                    );
+                }
+            } else {
+                quote!(paths.push(String::from(#name));)
+            });
+        }
+        if !field.nested && steps.iter().all(|step| *step == PathStep::Optional) {
+            continue;
+        }
+        // `Field` variant is taken by field of the type itself, so field named `field` gets `Field_`
+        let (variant, allow) = if *variant == "Field" {
+            (
+                format_ident!("Field_"),
+                quote!(#[allow(non_camel_case_types)]),
+            )
+        } else {
+            (variant.clone(), quote!())
+        };
+        let arm = PathArm {
+            pattern,
+            name,
+            steps: &steps,
+            nested: field.nested,
+            error_ident: &path_error_ident,
+            error_kind_ident: &path_error_kind_ident,
+        };
+        path_arms.push(arm.generate(quote!(#path_enum_ident::#variant), false));
+        if first {
+            path_variants.push(if field.nested {
+                let path_ty =
+                    generate_derived_type(elem_ty, "FieldPath", &field_lifetime, "nested")?;
+                quote!(#allow #variant(#path_ty))
+            } else {
+                quote!(#allow #variant(&#field_lifetime #elem_ty))
+            });
+        }
+        if field.mutable {
+            path_arms_mut.push(arm.generate(quote!(#path_enum_ident_mut::#variant), true));
+            let first_mut = !field_map[..idx]
+                .iter()
+                .any(|other| other.mutable && other.name == field.name);
+            if first_mut {
+                path_variants_mut.push(if field.nested {
+                    let path_ty =
+                        generate_derived_type(elem_ty, "FieldPathMut", &field_lifetime, "nested")?;
+                    quote!(#allow #variant(#path_ty))
+                } else {
+                    quote!(#allow #variant(&#field_lifetime mut #elem_ty))
+                });
+            }
+        }
+    }
+    for flatten in flattened
+        .iter()
+        .filter(|flatten| flatten.position == field_map.len())
+    {
+        let ty = &flatten.ty;
+        field_path_pushes.push(quote!(paths.extend(<#ty>::field_paths());));
+    }
+    let (by_name_wrap, by_name_fallback) = if is_enum {
+        (quote!(Some), quote!(_ => None))
+    } else {
+        (quote!(), quote!())
+    };
+    let by_name_ret = if is_enum {
+        quote!(Option<#value_enum_ident #enum_ty_generics>)
+    } else {
+        quote!(#value_enum_ident #enum_ty_generics)
+    };
+    let (by_name_wrap_mut, by_name_fallback_mut, by_name_ret_mut) =
+        if is_enum || mut_field_map.len() != field_map.len() {
+            (
+                quote!(Some),
+                quote!(_ => None),
+                quote!(Option<#value_enum_ident_mut #enum_ty_generics>),
+            )
+        } else {
+            (
+                quote!(),
+                quote!(),
+                quote!(#value_enum_ident_mut #enum_ty_generics),
+            )
+        };
+
+    let value_variant_idents = field_map
+        .iter()
+        .map(|field| &field.variant)
+        .unique()
+        .chain(flatten_variants.iter().copied())
+        .collect::<Vec<_>>();
+    let value_variant_names = value_variant_idents
+        .iter()
+        .map(|variant| variant.to_string());
+    let field_keys = field_map
+        .iter()
+        .map(|field| field.keys())
+        .collect::<Vec<_>>();
+    let field_indices = variants
+        .iter()
+        .flat_map(|variant| 0..variant.fields.len())
+        .collect::<Vec<_>>();
+    let mut_field_patterns = filter_mutable(&field_patterns, &field_map);
+    let mut_field_keys = filter_mutable(&field_keys, &field_map);
+    let mut_field_name_variants = filter_mutable(&field_name_variants, &field_map);
+    let mut_field_indices = filter_mutable(&field_indices, &field_map);
+    // Moving fields out of `self` is an error for types implementing `Drop`, so it's opt-in
+    let into_fields_impl = container.into_fields.then(|| {
+        quote! {
+            /// Method for taking value of field by its name, other fields are dropped
+            #visibility fn into_field(self, fieldname: &str) -> Option<#value_enum_ident_owned #ty_generics> {
+                match (self, fieldname) {
+                    #((#field_patterns, #field_keys) => Some(#field_values),)*
+                    #into_field_fallback
+                }
+            }
+            /// Method for destructuring into iterator over field names and their values
+            #visibility fn into_fields(self) -> impl Iterator<Item = (&'static str, #value_enum_ident_owned #ty_generics)> {
+                match self {
+                    #(#into_fields_arms,)*
+                }
+                .into_iter()
+            }
+        }
+    });
+
+    let (iter_len, variant_fields) = if is_enum {
+        let variant_count = variants.len();
+        let variant_paths = variants.iter().map(|variant| &variant.path);
+        let variant_names = variants
+            .iter()
+            .filter_map(|variant| variant.ident.as_ref().map(Ident::to_string));
+        let variant_field_lists = variants
+            .iter()
+            .map(|variant| {
+                let names = variant.fields.iter().map(|field| &field.name);
+                quote!(&[#(#names),*])
+            })
+            .collect::<Vec<_>>();
+        let variant_fields = quote! {
+            /// List with fields of every variant
+            #fields_vis const VARIANT_FIELDS: [(&'static str, &'static [&'static str]); #variant_count] = [
+                #((#variant_names, #variant_field_lists)),*
+            ];
+
+            /// Method for getting list with fields of the active variant
+            #visibility fn fields(&self) -> &'static [&'static str] {
+                match *self {
+                    #(#variant_paths { .. } => #variant_field_lists,)*
+                }
+            }
+        };
+        let iter_paths = variants.iter().map(|variant| &variant.path);
+        let iter_counts = variants
+            .iter()
+            .map(|variant| variant.fields.iter().filter(|field| field.iterable).count());
+        (
+            quote! {
+                match *self {
+                    #(#iter_paths { .. } => #iter_counts,)*
+                }
+            },
+            variant_fields,
+        )
+    } else {
+        (quote!(Self::ITER_FIELDS.len()), quote!())
+    };
+    let field_iter_at_body = generate_field_iter_at_body(&variants, &value_enum_ident);
+
+    let iter_impl = generate_iter_impl(
+        visibility,
+        &value_enum_ident,
+        &iter_ident,
+        struct_ident,
+        generics,
+        &enum_generics,
+        &field_lifetime,
+        &iter_len,
+    );
+
+    let (field_at_body, field_at_mut_body) = if flattened.is_empty() {
+        (
+            quote! {
+                match (self, idx) {
+                    #((#field_patterns, #field_indices) => Some(#field_refs),)*
+                    _ => None
+                }
+            },
+            quote! {
+                match (self, idx) {
+                    #((#mut_field_patterns, #mut_field_indices) => Some(#field_refs_mut),)*
+                    _ => None
+                }
+            },
+        )
+    } else {
+        (
+            quote!(Self::FIELDS
+                .get(idx)
+                .and_then(|fieldname| self.field(fieldname))),
+            quote!(Self::FIELDS
+                .get(idx)
+                .and_then(move |fieldname| self.field_mut(fieldname))),
+        )
+    };
+
+    let (fields_doc, field_at_doc) = if is_enum {
+        (
+            "List with all fields of all variants",
+            "position in the fields of the active variant",
+        )
+    } else {
+        ("List with all struct fields", "position in `FIELDS`")
+    };
+    let field_at_doc = format!(
+        "Method for getting reference to field by its {}",
+        field_at_doc
+    );
+
+    let tokens = quote! {
+        /// Enum with reference to possible field
+        #derive
+        #visibility enum #value_enum_ident #enum_generics #where_clauses {
+            #(#value_variants,)*
+        }
+
+        /// Enum with mutable reference to possible field
+        #derive_mut
+        #visibility enum #value_enum_ident_mut #enum_generics #where_clauses {
+            #(#value_variants_mut,)*
+        }
+
+        /// Enum with owned value of possible field
+        #derive_value
+        #[allow(dead_code)]
+        #visibility enum #value_enum_ident_owned #generics #where_clauses {
+            #(#value_variants_owned,)*
+        }
+
+        impl #impl_generics #value_enum_ident_owned #ty_generics #where_clauses {
+            /// Method for getting name of the variant
+            #visibility fn variant_name(&self) -> &'static str {
+                match *self {
+                    #(Self::#value_variant_idents(_) => #value_variant_names,)*
+                    #unused_variant_arm
+                }
+            }
+        }
+
+        #iter_impl
+
+        #flatten_check
+
+        #parse_impl
+
+        #markers
+
+        impl #impl_generics ::fieldname_access::FieldnameAccess for #struct_ident #ty_generics #where_clauses {
+            type Field<#field_lifetime> = #value_enum_ident #enum_ty_generics where Self: #field_lifetime;
+            type FieldMut<#field_lifetime> = #value_enum_ident_mut #enum_ty_generics where Self: #field_lifetime;
+            type FieldIter<#field_lifetime> = #iter_ident #enum_ty_generics where Self: #field_lifetime;
+
+            const FIELDS: &'static [&'static str] = &Self::FIELDS;
+
+            fn field<#field_lifetime>(&#field_lifetime self, fieldname: &str) -> Option<Self::Field<#field_lifetime>> {
+                Self::field(self, fieldname)
+            }
+            fn field_mut<#field_lifetime>(&#field_lifetime mut self, fieldname: &str) -> Option<Self::FieldMut<#field_lifetime>> {
+                Self::field_mut(self, fieldname)
+            }
+            fn field_iter<#field_lifetime>(&#field_lifetime self) -> Self::FieldIter<#field_lifetime> {
+                Self::field_iter(self)
+            }
+        }
+
+        /// Enum with reference to field or element found by path
+        #derive
+        #visibility enum #path_enum_ident #enum_generics #where_clauses {
+            /// Field of this type
+            Field(#value_enum_ident #enum_ty_generics),
+            #(#path_variants,)*
+        }
+
+        /// Enum with mutable reference to field or element found by path
+        #derive_mut
+        #visibility enum #path_enum_ident_mut #enum_generics #where_clauses {
+            /// Field of this type
+            Field(#value_enum_ident_mut #enum_ty_generics),
+            #(#path_variants_mut,)*
+        }
+
+        /// Enum with names of all fields
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #visibility enum #name_enum_ident {
+            #(#name_variants,)*
+        }
+
+        impl #name_enum_ident {
+            /// List with all field names in the order of `FIELDS`, fields of flattened fields aren't included
+            #visibility const ALL: [Self; #name_count] = [#(Self::#name_variants),*];
+
+            /// Method for getting name of the field
+            #visibility const fn as_str(&self) -> &'static str {
+                match *self {
+                    #(Self::#name_variants => #field_list,)*
+                }
+            }
+
+            /// Method for getting position of the field in `ALL`
+            #visibility const fn index(&self) -> usize {
+                *self as usize
+            }
+        }
+
+        impl ::std::fmt::Display for #name_enum_ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl ::std::str::FromStr for #name_enum_ident {
+            type Err = #name_error_ident;
+
+            fn from_str(fieldname: &str) -> Result<Self, Self::Err> {
+                match fieldname {
+                    #(#field_list => Ok(Self::#name_variants),)*
+                    #(#alias_names => Self::from_str(#alias_canonicals),)*
+                    _ => #name_from_str_fallback,
+                }
+            }
+        }
+
+        impl ::std::convert::TryFrom<usize> for #name_enum_ident {
+            type Error = #name_error_ident;
+
+            fn try_from(idx: usize) -> Result<Self, Self::Error> {
+                Self::ALL
+                    .get(idx)
+                    .copied()
+                    .ok_or(#name_error_ident::OutOfRange(idx))
+            }
+        }
+
+        impl #impl_generics #struct_ident #ty_generics #where_clauses {
+            #[doc = #fields_doc]
+            #fields_vis const FIELDS: [&'static str; #fields_len] = #fields_const;
+            /// Number of fields in `FIELDS`
+            #fields_vis const FIELD_COUNT: usize = #fields_len;
+            /// Pairs of alias and field name it resolves into, used by structs flattening this one
+            #[doc(hidden)]
+            #fields_vis const FIELD_ALIASES: [(&'static str, &'static str); #aliases_len] = #aliases_const;
+            #iter_fields_const
+
+            #variant_fields
+
+            /// Method for resolving field name or its alias into the name listed in `FIELDS` using `lookup` mode
+            #visibility fn canonical_name(fieldname: &str) -> Option<&'static str> {
+                match fieldname {
+                    #(#field_list => Some(#field_list),)*
+                    #(#alias_names => Some(#alias_canonicals),)*
+                    _ => #canonical_fallback
+                }
+            }
+            /// Method for checking whether the name is a deprecated alias of some field using `lookup` mode
+            #visibility fn is_deprecated_alias(fieldname: &str) -> bool {
+                #is_alias
+            }
+
+            /// Method for getting reference to field by its name
+            #visibility fn field<#field_lifetime>(&#field_lifetime self, fieldname: &str) -> Option<#value_enum_ident #enum_ty_generics> {
+                match (self, fieldname) {
+                    #((#field_patterns, #field_keys) => Some(#field_refs),)*
+                    #field_fallback
+                }
+            }
+            /// Method for getting mutable reference to field by its name
+            #visibility fn field_mut<#field_lifetime>(&#field_lifetime mut self, fieldname: &str) -> Option<#value_enum_ident_mut #enum_ty_generics> {
+                match (self, fieldname) {
+                    #((#mut_field_patterns, #mut_field_keys) => Some(#field_refs_mut),)*
+                    #field_mut_fallback
+                }
+            }
+            /// Method for getting reference to field by path like `items[3].price` or `labels["env"]`
+            /// through `#[fieldname(nested)]` fields and collections
+            #visibility fn field_path<#field_lifetime>(&#field_lifetime self, path: &str) -> Result<#path_enum_ident #enum_ty_generics, #path_error_ident> {
+                let (head, rest) = path.split_at(path.find(|c: char| c == '.' || c == '[').unwrap_or(path.len()));
+                if rest.is_empty() {
+                    return self.field(head).map(#path_enum_ident::Field).ok_or_else(|| #path_error_ident {
+                        path: head.to_string(),
+                        kind: #path_error_kind_ident::UnknownField,
+                    });
+                }
+                match (self, Self::canonical_name(head)) {
+                    #(#path_arms)*
+                    (this, _) => {
+                        let kind = if this.field(head).is_some() {
+                            #path_error_kind_ident::NotNavigable
+                        } else {
+                            #path_error_kind_ident::UnknownField
+                        };
+                        Err(#path_error_ident { path: head.to_string(), kind })
+                    }
+                }
+            }
+            /// Method for getting mutable reference to field by path like `items[3].price` or `labels["env"]`
+            /// through `#[fieldname(nested)]` fields and collections
+            #visibility fn field_path_mut<#field_lifetime>(&#field_lifetime mut self, path: &str) -> Result<#path_enum_ident_mut #enum_ty_generics, #path_error_ident> {
+                let (head, rest) = path.split_at(path.find(|c: char| c == '.' || c == '[').unwrap_or(path.len()));
+                if rest.is_empty() {
+                    return self.field_mut(head).map(#path_enum_ident_mut::Field).ok_or_else(|| #path_error_ident {
+                        path: head.to_string(),
+                        kind: #path_error_kind_ident::UnknownField,
+                    });
+                }
+                match (self, Self::canonical_name(head)) {
+                    #(#path_arms_mut)*
+                    (this, _) => {
+                        let kind = if this.field_mut(head).is_some() {
+                            #path_error_kind_ident::NotNavigable
+                        } else {
+                            #path_error_kind_ident::UnknownField
+                        };
+                        Err(#path_error_ident { path: head.to_string(), kind })
+                    }
+                }
+            }
+            /// Method for getting paths of all fields, fields of `#[fieldname(nested)]` fields are listed instead of them
+            /// with `[]` in place of indexes and keys of collections
+            #visibility fn field_paths() -> Vec<String> {
+                let mut paths = Vec::new();
+                #(#field_path_pushes)*
+                paths
+            }
+            /// Method for getting reference to field by its name with suggestions of similar names on error
+            #visibility fn try_field<#field_lifetime>(&#field_lifetime self, fieldname: &str) -> Result<#value_enum_ident #enum_ty_generics, #unknown_field_ident> {
+                let (type_name, valid): (&'static str, &'static [&'static str]) = #lookup_target;
+                self.field(fieldname)
+                    .ok_or_else(|| #unknown_field_ident::new(fieldname, type_name, valid))
+            }
+            /// Method for getting mutable reference to field by its name with suggestions of similar names on error
+            #visibility fn try_field_mut<#field_lifetime>(&#field_lifetime mut self, fieldname: &str) -> Result<#value_enum_ident_mut #enum_ty_generics, #unknown_field_ident> {
+                let (type_name, valid): (&'static str, &'static [&'static str]) = #lookup_target_mut;
+                self.field_mut(fieldname)
+                    .ok_or_else(|| #unknown_field_ident::new(fieldname, type_name, valid))
+            }
+            /// Method for getting reference to field by its typed name
+            #visibility fn field_by_name<#field_lifetime>(&#field_lifetime self, fieldname: #name_enum_ident) -> #by_name_ret {
+                match (self, fieldname) {
+                    #((#field_patterns, #name_enum_ident::#field_name_variants) => #by_name_wrap(#field_refs),)*
+                    #by_name_fallback
+                }
+            }
+            /// Method for getting mutable reference to field by its typed name
+            #visibility fn field_by_name_mut<#field_lifetime>(&#field_lifetime mut self, fieldname: #name_enum_ident) -> #by_name_ret_mut {
+                match (self, fieldname) {
+                    #((#mut_field_patterns, #name_enum_ident::#mut_field_name_variants) => #by_name_wrap_mut(#field_refs_mut),)*
+                    #by_name_fallback_mut
+                }
+            }
+            /// Method for creating iterator over mutable references to fields yielded by `field_iter`
+            /// except `#[fieldname(skip_mut)]` fields
+            #visibility fn field_iter_mut<#field_lifetime>(&#field_lifetime mut self) -> impl Iterator<Item = (&'static str, #value_enum_ident_mut #enum_ty_generics)> {
+                match self {
+                    #(#iter_mut_arms,)*
+                }
+                .into_iter()
+            }
+            #into_fields_impl
+            /// Method for setting field by its name, returns the old value
+            #[allow(unreachable_patterns)]
+            #visibility fn set_field(&mut self, fieldname: &str, value: #value_enum_ident_owned #ty_generics) -> Result<#value_enum_ident_owned #ty_generics, #set_error_ident> {
+                match (self, fieldname) {
+                    #(#set_field_arms,)*
+                    #set_field_fallback
+                }
+            }
+            /// Method for getting field with its name by position in fields yielded by `field_iter`
+            fn field_iter_at<#field_lifetime>(&#field_lifetime self, idx: usize) -> Option<(&'static str, #value_enum_ident #enum_ty_generics)> {
+                #field_iter_at_body
+            }
+            #[doc = #field_at_doc]
+            #visibility fn field_at<#field_lifetime>(&#field_lifetime self, idx: usize) -> Option<#value_enum_ident #enum_ty_generics> {
+                #field_at_body
+            }
+            #[doc = #field_at_doc]
+            #visibility fn field_at_mut<#field_lifetime>(&#field_lifetime mut self, idx: usize) -> Option<#value_enum_ident_mut #enum_ty_generics> {
+                #field_at_mut_body
+            }
+        }
+    };
+    Ok(tokens)
+}
+
+/// Struct or enum variant with its fields prepared for code generation
+struct VariantInfo {
+    /// Name of enum variant, `None` for structs
+    ident: Option<Ident>,
+    /// Path used in patterns, `Self` for structs and `Self::Variant` for enum variants
+    path: proc_macro2::TokenStream,
+    fields: Vec<FieldInfo>,
+    /// Fields marked with `#[fieldname(flatten)]`, only structs have them
+    flattened: Vec<FlattenInfo>,
+}
+
+/// Struct field which fields are accessible as fields of the struct
+struct FlattenInfo {
+    member: Member,
+    ty: Type,
+    /// Variant of generated enums holding fields of the field
+    variant: Ident,
+    /// Variant is generated from the type, not set with `#[fieldname = "..."]`
+    generated_variant: bool,
+    /// Number of regular fields declared before the field
+    position: usize,
+    mutable: bool,
+    iterable: bool,
+    span: Span,
+}
+
+/// Struct field prepared for code generation
+struct FieldInfo {
+    /// Accessor of the field, `self.#member`
+    member: Member,
+    /// Name used for runtime access and listed in `FIELDS`
+    name: String,
+    /// Deprecated names also accepted by lookups
+    aliases: Vec<String>,
+    ty: Type,
+    /// Variant of generated enums holding the field
+    variant: Ident,
+    /// Variant is generated from the type, not set with `#[fieldname = "..."]`
+    generated_variant: bool,
+    /// How `set_field_str` parses the field
+    parse: FieldParse,
+    /// Field is accessible mutably, `false` with `#[fieldname(skip_mut)]`
+    mutable: bool,
+    /// Field is yielded by `field_iter`, `false` with `#[fieldname(skip_iter)]`
+    iterable: bool,
+    /// Field type derives `FieldnameAccess` and is accessible by path with `#[fieldname(nested)]`
+    nested: bool,
+    /// Visibility of the field, also used by its marker type
+    vis: Visibility,
+    span: Span,
+}
+
+impl FieldInfo {
+    /// Pattern matching the name of the field or any of its aliases
+    fn keys(&self) -> proc_macro2::TokenStream {
+        let Self { name, aliases, .. } = self;
+        quote!(#name #(| #aliases)*)
+    }
+}
+
+enum FieldParse {
+    FromStr,
+    With(syn::Path),
+    Disabled,
+}
+
+fn collect_fields(
+    fields: &Fields,
+    container: &ContainerAttrs,
+) -> syn::Result<(Vec<FieldInfo>, Vec<FlattenInfo>)> {
+    let mut names: Vec<String> = Vec::new();
+    let mut collected = Vec::new();
+    let mut flattened = Vec::new();
+    let per_type = container.variants == VariantMode::PerType;
+    for (idx, field) in fields.iter().enumerate() {
+        let attrs = FieldAttrs::from_attrs(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let span = field
+            .ident
+            .as_ref()
+            .map_or_else(|| field.ty.span(), Ident::span);
+        if attrs.flatten {
+            let member = field
+                .ident
+                .clone()
+                .map_or_else(|| Member::Unnamed(Index::from(idx)), Member::Named);
+            flattened.push(FlattenInfo {
+                ty: field.ty.clone(),
+                generated_variant: attrs.variant.is_none() && per_type,
+                variant: match attrs.variant {
+                    Some(variant) => variant,
+                    None if per_type => generate_variant_name(&field.ty)?,
+                    None => field_variant_name(&member, &idx.to_string(), span)?,
+                },
+                member,
+                position: collected.len(),
+                mutable: !attrs.skip_mut,
+                iterable: !attrs.skip_iter,
+                span,
+            });
+            continue;
+        }
+        let (member, name) = match (&field.ident, attrs.name) {
+            (Some(_), Some(name)) => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "`name` is only supported on tuple fields",
+                ))
+            }
+            (Some(ident), None) => {
+                let name = ident.unraw().to_string();
+                let name = match container.rename_all {
+                    Some(rule) => rule.apply(&name),
+                    None => name,
+                };
+                (Member::Named(ident.clone()), name)
+            }
+            (None, name) => (
+                Member::Unnamed(Index::from(idx)),
+                name.map_or_else(|| idx.to_string(), |name| name.value()),
+            ),
+        };
+        let name = attrs.rename.map_or(name, |rename| rename.value());
+        if names.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("Duplicate field name `{}`", name),
+            ));
+        }
+        names.push(name.clone());
+        let ty = field.ty.clone();
+        let generated_variant = attrs.variant.is_none() && per_type;
+        let variant = match attrs.variant {
+            Some(variant) => variant,
+            None if per_type => generate_variant_name(&ty)?,
+            None => field_variant_name(&member, &name, span)?,
+        };
+        let parse = match (attrs.no_parse, attrs.parse_with) {
+            (Some(key), _) if !container.parse => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "`no_parse` requires `#[fieldname_enum(parse)]` on the container",
+                ))
+            }
+            (_, Some(path)) if !container.parse => {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "`parse_with` requires `#[fieldname_enum(parse)]` on the container",
+                ))
+            }
+            _ if attrs.skip_mut => FieldParse::Disabled,
+            (Some(_), _) => FieldParse::Disabled,
+            (None, Some(path)) => FieldParse::With(path),
+            (None, None) => FieldParse::FromStr,
+        };
+        let mut aliases: Vec<String> = Vec::new();
+        for alias in attrs.aliases {
+            if alias.value() == name || aliases.contains(&alias.value()) {
+                return Err(syn::Error::new(
+                    alias.span(),
+                    format!("Duplicate alias `{}`", alias.value()),
+                ));
+            }
+            aliases.push(alias.value());
+        }
+        collected.push(FieldInfo {
+            member,
+            name,
+            aliases,
+            ty,
+            variant,
+            generated_variant,
+            parse,
+            mutable: !attrs.skip_mut,
+            iterable: !attrs.skip_iter,
+            nested: attrs.nested,
+            vis: field.vis.clone(),
+            span,
+        });
+    }
+    Ok((collected, flattened))
+}
+
+fn generate_variant_name(ty: &syn::Type) -> syn::Result<Ident> {
+    type_variant_name(ty, 0, false)
+        .and_then(|name| syn::parse_str::<Ident>(&name).ok())
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                ty,
+                "Cannot generate variant name for this type, specify it with `#[fieldname = \"...\"]`",
+            )
+        })
+}
+
+/// Name of variant holding `ty` built from its structure, paths keep `qualify` segments
+/// before the last one. `&'a [u8; 4]` becomes `RefArray4U8`, or `RefAArray4U8` with `lifetimes`,
+/// and `(a::Id, ())` with `qualify` of 1 becomes `TupleAIdUnit`
+fn type_variant_name(ty: &Type, qualify: usize, lifetimes: bool) -> Option<String> {
+    let name = |ty: &Type| type_variant_name(ty, qualify, lifetimes);
+    let names = |tys: &mut dyn Iterator<Item = &Type>| {
+        tys.map(|ty| type_variant_name(ty, qualify, lifetimes))
+            .collect::<Option<String>>()
+    };
+    Some(match ty {
+        Type::Path(syn::TypePath { qself, path }) => {
+            let qself = match qself {
+                Some(qself) => name(&qself.ty)?,
+                None => String::new(),
+            };
+            qself + &path_variant_name(path, qualify, lifetimes)?
+        }
+        Type::Reference(reference) => {
+            let lifetime = reference
+                .lifetime
+                .as_ref()
+                .filter(|_| lifetimes)
+                .map(lifetime_variant_name)
+                .unwrap_or_default();
+            match reference.mutability {
+                Some(_) => format!("RefMut{}{}", lifetime, name(&reference.elem)?),
+                None => format!("Ref{}{}", lifetime, name(&reference.elem)?),
+            }
+        }
+        Type::Array(array) => format!(
+            "Array{}{}",
+            const_variant_name(&array.len).unwrap_or_default(),
+            name(&array.elem)?
+        ),
+        Type::Slice(slice) => format!("Slice{}", name(&slice.elem)?),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => String::from("Unit"),
+        Type::Tuple(tuple) => format!("Tuple{}", names(&mut tuple.elems.iter())?),
+        Type::Ptr(ptr) => match ptr.mutability {
+            Some(_) => format!("MutPtr{}", name(&ptr.elem)?),
+            None => format!("ConstPtr{}", name(&ptr.elem)?),
+        },
+        Type::BareFn(function) => format!(
+            "Fn{}{}",
+            names(&mut function.inputs.iter().map(|arg| &arg.ty))?,
+            return_variant_name(&function.output, qualify, lifetimes)?
+        ),
+        Type::TraitObject(object) => {
+            let traits = object
+                .bounds
+                .iter()
+                .filter_map(|bound| match bound {
+                    syn::TypeParamBound::Trait(bound) => {
+                        Some(path_variant_name(&bound.path, qualify, lifetimes))
+                    }
+                    syn::TypeParamBound::Lifetime(lifetime) if lifetimes => {
+                        Some(Some(lifetime_variant_name(lifetime)))
+                    }
+                    _ => None,
+                })
+                .collect::<Option<String>>()?;
+            format!("Dyn{}", traits)
+        }
+        Type::Paren(paren) => name(&paren.elem)?,
+        Type::Group(group) => name(&group.elem)?,
+        Type::Never(_) => String::from("Never"),
+        _ => return None,
+    })
+}
+
+/// Errors for fields of different types which have the same variant of generated enums,
+/// flattened fields never share variant with regular fields
+fn check_variant_collisions(variants: &[VariantInfo]) -> syn::Result<()> {
+    let mut seen: Vec<(&Ident, String, String, Span)> = Vec::new();
+    let holders = variants.iter().flat_map(|variant| {
+        variant
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    &field.variant,
+                    field.name.clone(),
+                    display_type(&field.ty),
+                    field.span,
+                )
+            })
+            .chain(variant.flattened.iter().map(|flatten| {
+                (
+                    &flatten.variant,
+                    flatten.member.to_token_stream().to_string(),
+                    format!("flattened {}", display_type(&flatten.ty)),
+                    flatten.span,
+                )
+            }))
+    });
+    for (variant, name, ty, span) in holders {
+        match seen.iter().find(|(other, ..)| *other == variant) {
+            Some((_, other_name, other_ty, other_span)) if *other_ty != ty => {
+                let mut error = syn::Error::new(
+                    span,
+                    format!(
+                        "Fields `{}` and `{}` of different types `{}` and `{}` have the same `{}` variant, \
+                         specify another one with `#[fieldname = \"...\"]`",
+                        other_name, name, other_ty, ty, variant
+                    ),
+                );
+                error.combine(syn::Error::new(
+                    *other_span,
+                    format!(
+                        "`{}` variant is also used by field `{}`",
+                        variant, other_name
+                    ),
+                ));
+                return Err(error);
+            }
+            Some(_) => {}
+            None => seen.push((variant, name, ty, span)),
+        }
+    }
+    Ok(())
+}
+
+/// Type for error messages without spaces which `to_string` of tokens puts between all of them
+fn display_type(ty: &Type) -> String {
+    [
+        (" < ", "<"),
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        ("& ", "&"),
+        (" ,", ","),
+        (" :: ", "::"),
+        (":: ", "::"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+        ("( ", "("),
+        (" )", ")"),
+    ]
+    .iter()
+    .fold(ty.to_token_stream().to_string(), |ty, (from, to)| {
+        ty.replace(from, to)
+    })
+}
+
+/// Name of path segments in `PascalCase` followed by names of their generic arguments,
+/// lifetimes are omitted unless `lifetimes` is set
+fn path_variant_name(path: &syn::Path, qualify: usize, lifetimes: bool) -> Option<String> {
+    let skip = path.segments.len().saturating_sub(qualify + 1);
+    let mut name = String::new();
+    for segment in path.segments.iter().skip(skip) {
+        name += &to_pascal_case(&segment.ident.unraw().to_string());
+        match &segment.arguments {
+            syn::PathArguments::None => {}
+            syn::PathArguments::AngleBracketed(args) => {
+                for arg in &args.args {
+                    match arg {
+                        syn::GenericArgument::Type(ty) => {
+                            name += &type_variant_name(ty, qualify, lifetimes)?
+                        }
+                        syn::GenericArgument::AssocType(assoc) => {
+                            name += &type_variant_name(&assoc.ty, qualify, lifetimes)?
+                        }
+                        syn::GenericArgument::Const(expr) => name += &const_variant_name(expr)?,
+                        syn::GenericArgument::Lifetime(lifetime) if lifetimes => {
+                            name += &lifetime_variant_name(lifetime)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            syn::PathArguments::Parenthesized(args) => {
+                for ty in &args.inputs {
+                    name += &type_variant_name(ty, qualify, lifetimes)?;
+                }
+                name += &return_variant_name(&args.output, qualify, lifetimes)?;
+            }
+        }
+    }
+    Some(name)
+}
+
+/// `To` with name of the returned type, empty for functions returning `()`
+fn return_variant_name(
+    output: &syn::ReturnType,
+    qualify: usize,
+    lifetimes: bool,
+) -> Option<String> {
+    match output {
+        syn::ReturnType::Default => Some(String::new()),
+        syn::ReturnType::Type(_, ty) => {
+            Some(format!("To{}", type_variant_name(ty, qualify, lifetimes)?))
+        }
+    }
+}
+
+/// Name of lifetime in `PascalCase`, `'a` becomes `A`
+fn lifetime_variant_name(lifetime: &syn::Lifetime) -> String {
+    to_pascal_case(&lifetime.ident.unraw().to_string())
+}
+
+/// Name of const generic argument or array length which is a literal or a parameter
+fn const_variant_name(expr: &syn::Expr) -> Option<String> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => Some(int.base10_digits().to_string()),
+        syn::Expr::Path(syn::ExprPath {
+            qself: None, path, ..
+        }) => path
+            .get_ident()
+            .map(|ident| to_pascal_case(&ident.to_string())),
+        syn::Expr::Block(block) => match block.block.stmts.as_slice() {
+            [syn::Stmt::Expr(expr, None)] => const_variant_name(expr),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Qualifies names of variants generated for different types which got the same name
+/// with segments of their paths, `a::Id` and `b::Id` become `AId` and `BId`. Types which
+/// differ only in lifetimes get them in names instead, `&'a str` and `&'b str` become
+/// `RefAStr` and `RefBStr`
+fn disambiguate_variants(variants: &mut [VariantInfo]) -> syn::Result<()> {
+    let mut generated = variants
+        .iter_mut()
+        .flat_map(
+            |VariantInfo {
+                 fields, flattened, ..
+             }| {
+                fields
+                    .iter_mut()
+                    .filter(|field| field.generated_variant)
+                    .map(|field| (&field.ty, &mut field.variant))
+                    .chain(
+                        flattened
+                            .iter_mut()
+                            .filter(|flatten| flatten.generated_variant)
+                            .map(|flatten| (&flatten.ty, &mut flatten.variant)),
+                    )
+            },
+        )
+        .collect::<Vec<_>>();
+    let names = generated
+        .iter()
+        .map(|(_, variant)| variant.to_string())
+        .collect::<Vec<_>>();
+    for name in names.iter().unique() {
+        let group = (0..generated.len())
+            .filter(|idx| names[*idx] == *name)
+            .collect::<Vec<_>>();
+        let types = group
+            .iter()
+            .map(|idx| generated[*idx].0.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+        if types.iter().all_equal() {
+            continue;
+        }
+        let mut resolved = None;
+        'attempts: for lifetimes in [false, true] {
+            let mut previous = None;
+            for qualify in usize::from(!lifetimes).. {
+                let qualified = group
+                    .iter()
+                    .map(|idx| {
+                        type_variant_name(generated[*idx].0, qualify, lifetimes).unwrap_or_default()
+                    })
+                    .collect::<Vec<_>>();
+                let distinct = types
+                    .iter()
+                    .zip(&qualified)
+                    .unique()
+                    .map(|(_, qualified)| qualified)
+                    .all_unique();
+                if distinct {
+                    resolved = Some(qualified);
+                    break 'attempts;
+                }
+                if previous.as_ref() == Some(&qualified) {
+                    break;
+                }
+                previous = Some(qualified);
+            }
+        }
+        match resolved {
+            Some(qualified) => {
+                for (idx, qualified) in group.iter().zip(qualified) {
+                    *generated[*idx].1 = format_ident!("{}", qualified);
+                }
+            }
+            None => {
+                let other = types
+                    .iter()
+                    .position(|ty| *ty != types[0])
+                    .expect("group has different types");
+                return Err(syn::Error::new_spanned(
+                    generated[group[other]].0,
+                    format!(
+                        "Types `{}` and `{}` get the same `{}` variant name, specify it with `#[fieldname = \"...\"]`",
+                        display_type(generated[group[0]].0),
+                        display_type(generated[group[other]].0),
+                        name
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_iter_impl(
+    vis: &Visibility,
+    value_enum_ident: &Ident,
+    iter_ident: &Ident,
+    struct_ident: &Ident,
+    generics: &Generics,
+    enum_generics: &Generics,
+    enum_lt: &syn::GenericParam,
+    iter_len: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, struct_generics, where_clauses) = generics.split_for_impl();
+    let (enum_impl_generics, enum_ty_generics, _) = enum_generics.split_for_impl();
+
+    quote! {
+        /// Iterator over fields yielded by `field_iter` dispatching on their positions
+        #vis struct #iter_ident #enum_generics #where_clauses {
+            front: usize,
+            back: usize,
+            inner: &#enum_lt #struct_ident #struct_generics
+        }
+
+        impl #impl_generics #struct_ident #struct_generics #where_clauses {
+            pub fn field_iter<#enum_lt>(&#enum_lt self) -> #iter_ident #enum_ty_generics {
+                #iter_ident {
+                    front: 0,
+                    back: #iter_len,
+                    inner: self
+                }
+            }
+        }
+
+        impl #enum_impl_generics Clone for #iter_ident #enum_ty_generics #where_clauses {
+            fn clone(&self) -> Self {
+                Self {
+                    front: self.front,
+                    back: self.back,
+                    inner: self.inner,
+                }
+            }
+        }
+
+        impl #enum_impl_generics Iterator for #iter_ident #enum_ty_generics #where_clauses {
+            type Item = (&'static str, #value_enum_ident #enum_ty_generics);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                (self.front != self.back).then(|| {
+                    self.front += 1;
+                    self.inner.field_iter_at(self.front - 1).unwrap()
+                })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.back - self.front, Some(self.back - self.front))
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.front += n.min(self.back - self.front);
+                self.next()
+            }
+        }
+
+        impl #enum_impl_generics DoubleEndedIterator for #iter_ident #enum_ty_generics #where_clauses {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                (self.front != self.back).then(|| {
+                    self.back -= 1;
+                    self.inner.field_iter_at(self.back).unwrap()
+                })
+            }
+        }
+
+        impl #enum_impl_generics ExactSizeIterator for #iter_ident #enum_ty_generics #where_clauses {}
+
+        impl #enum_impl_generics ::std::iter::FusedIterator for #iter_ident #enum_ty_generics #where_clauses {}
+    }
+}
+
+/// Body of `field_iter_at` getting field by its position in fields yielded by `field_iter`,
+/// positions after flattened field are shifted by the number of its iterable fields
+fn generate_field_iter_at_body(
+    variants: &[VariantInfo],
+    value_enum_ident: &Ident,
+) -> proc_macro2::TokenStream {
+    let flattened = variants
+        .iter()
+        .flat_map(|variant| &variant.flattened)
+        .filter(|flatten| flatten.iterable)
+        .collect::<Vec<_>>();
+    if flattened.is_empty() {
+        let arms = variants.iter().flat_map(|VariantInfo { path, fields, .. }| {
+            fields.iter().filter(|field| field.iterable).enumerate().map(
+                move |(idx, FieldInfo { member, name, variant, .. })| {
+                    quote! {
+                        (#path { #member: field, .. }, #idx) => Some((#name, #value_enum_ident::#variant(field)))
+                    }
+                },
+            )
+        });
+        return quote! {
+            match (self, idx) {
+                #(#arms,)*
+                _ => None
+            }
+        };
+    }
+    // Only structs have flattened fields
+    let fields = &variants[0].fields;
+    let mut end = quote!(0);
+    let mut stmts = Vec::new();
+    for position in 0..=fields.len() {
+        for FlattenInfo {
+            member,
+            ty,
+            variant,
+            ..
+        } in flattened
+            .iter()
+            .filter(|flatten| flatten.position == position)
+        {
+            let start = end;
+            end = quote!(#start + <#ty>::ITER_FIELDS.len());
+            stmts.push(quote! {
+                if idx < #end {
+                    return self
+                        .#member
+                        .field_iter()
+                        .nth(idx - (#start))
+                        .map(|(name, field)| (name, #value_enum_ident::#variant(field)));
+                }
+            });
+        }
+        if let Some(FieldInfo {
+            member,
+            name,
+            variant,
+            ..
+        }) = fields.get(position).filter(|field| field.iterable)
+        {
+            end = quote!(#end + 1);
+            stmts.push(quote! {
+                if idx < #end {
+                    return Some((#name, #value_enum_ident::#variant(&self.#member)));
+                }
+            });
+        }
+    }
+    quote! {
+        #(#stmts)*
+        None
+    }
+}
+
+/// Variants of `<Struct>FieldName` enum for every field, named fields use their Rust identifiers
+fn generate_name_variants(field_map: &[&FieldInfo]) -> syn::Result<Vec<Ident>> {
+    let mut variants: Vec<(&str, Ident)> = Vec::new();
+    for field in field_map {
+        let variant = field_variant_name(&field.member, &field.name, field.span)?;
+        if let Some((other, _)) = variants
+            .iter()
+            .find(|(other, other_variant)| *other != field.name && *other_variant == variant)
+        {
+            return Err(syn::Error::new(
+                field.span,
+                format!(
+                    "Fields `{}` and `{}` have the same `{}` name variant",
+                    other, field.name, variant
+                ),
+            ));
+        }
+        variants.push((&field.name, variant));
+    }
+    Ok(variants.into_iter().map(|(_, variant)| variant).collect())
+}
+
+/// Variant named after the field, its identifier for named fields and `name` for tuple ones
+fn field_variant_name(member: &Member, name: &str, span: Span) -> syn::Result<Ident> {
+    let pascal = match member {
+        Member::Named(ident) => to_pascal_case(&ident.unraw().to_string()),
+        Member::Unnamed(_) => to_pascal_case(name),
+    };
+    syn::parse_str::<Ident>(&pascal)
+        .or_else(|_| syn::parse_str::<Ident>(&format!("Field{}", pascal)))
+        .map_err(|_| {
+            syn::Error::new(
+                span,
+                format!("Cannot generate variant name for field `{}`", name),
+            )
+        })
+}
+
+/// `module` with zero-sized marker type of every field implementing `Field` traits of the runtime
+/// crate, which `get` and `get_mut` of `FieldnameAccessExt` take. Markers are as visible as their
+/// fields, so field types don't leak from public interface
+fn generate_markers(
+    vis: &Visibility,
+    struct_ident: &Ident,
+    generics: &Generics,
+    field_map: &[&FieldInfo],
+    markers: &[Ident],
+    module: &Ident,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clauses) = generics.split_for_impl();
+    let marker_vis = field_map.iter().map(|field| nested_visibility(&field.vis));
+    let module_doc = format!(
+        "Marker types of `{}` fields taken by `get` and `get_mut` of `FieldnameAccessExt`",
+        struct_ident
+    );
+    let marker_docs = field_map
+        .iter()
+        .map(|field| format!("Marker of `{}` field", field.name));
+    let impls = field_map.iter().zip(markers).map(|(field, marker)| {
+        let FieldInfo {
+            member, name, ty, ..
+        } = field;
+        let field_mut_impl = field.mutable.then(|| {
+            quote! {
+                impl #impl_generics ::fieldname_access::FieldMut<#struct_ident #ty_generics> for #module::#marker #where_clauses {
+                    fn get_mut<'owner>(owner: &'owner mut #struct_ident #ty_generics) -> &'owner mut Self::Value {
+                        &mut owner.#member
+                    }
+                }
+            }
+        });
+        quote! {
+            impl #impl_generics ::fieldname_access::Field<#struct_ident #ty_generics> for #module::#marker #where_clauses {
+                type Value = #ty;
+                const NAME: &'static str = #name;
+                fn get<'owner>(owner: &'owner #struct_ident #ty_generics) -> &'owner Self::Value {
+                    &owner.#member
+                }
+            }
+            #field_mut_impl
+        }
+    });
+
+    quote! {
+        #[doc = #module_doc]
+        #[allow(non_snake_case)]
+        #vis mod #module {
+            #(
+                #[doc = #marker_docs]
+                #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+                #marker_vis struct #markers;
+            )*
+        }
+
+        #(#impls)*
+    }
+}
+
+/// Visibility inside of a child module equal to `vis` in the parent one
+fn nested_visibility(vis: &Visibility) -> proc_macro2::TokenStream {
+    match vis {
+        Visibility::Inherited => quote!(pub(super)),
+        Visibility::Restricted(restricted) => {
+            let path = &restricted.path;
+            if path.is_ident("self") {
+                quote!(pub(super))
+            } else if path.is_ident("super") {
+                quote!(pub(in super::super))
+            } else if path
+                .segments
+                .first()
+                .map_or(false, |first| first.ident == "self")
+            {
+                let rest = path.segments.iter().skip(1);
+                quote!(pub(in super #(::#rest)*))
+            } else if path
+                .segments
+                .first()
+                .map_or(false, |first| first.ident == "super")
+            {
+                quote!(pub(in super::#path))
+            } else {
+                vis.to_token_stream()
+            }
+        }
+        Visibility::Public(_) => vis.to_token_stream(),
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::new();
+    for (idx, ch) in chars.iter().enumerate() {
+        if ch.is_ascii_uppercase() {
+            let previous = idx.checked_sub(1).map(|idx| chars[idx]);
+            let next = chars.get(idx + 1);
+            let boundary = previous.map_or(false, |previous| {
+                previous.is_ascii_lowercase()
+                    || previous.is_ascii_digit()
+                    || previous.is_ascii_uppercase() && next.map_or(false, char::is_ascii_lowercase)
+            });
+            if boundary {
+                snake.push('_');
+            }
+            snake.push(ch.to_ascii_lowercase());
+        } else {
+            snake.push(*ch);
+        }
+    }
+    snake
+}
+
+fn to_pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter_map(|segment| {
+            let mut chars = segment.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        })
+        .collect()
+}
+
+fn generate_enum_variants(
+    field_map: &[&FieldInfo],
+    reference: &proc_macro2::TokenStream,
+) -> Vec<proc_macro2::TokenStream> {
+    field_map
+        .iter()
+        .unique_by(|field| &field.variant)
+        .map(|FieldInfo { ty, variant, .. }| {
+            quote! {
+                #variant(#reference #ty)
+            }
+        })
+        .collect()
+}
+
+/// Patterns binding each field of each variant to `field`
+fn generate_field_patterns(variants: &[VariantInfo]) -> Vec<proc_macro2::TokenStream> {
+    variants
+        .iter()
+        .flat_map(|VariantInfo { path, fields, .. }| {
+            fields.iter().map(move |FieldInfo { member, .. }| {
+                quote! {
+                    #path { #member: field, .. }
+                }
+            })
+        })
+        .collect()
+}
+
+/// Arms destructuring each variant into `Vec` of field names and their owned values,
+/// fields of flattened fields are inserted at their positions
+fn generate_into_fields_arms(
+    variants: &[VariantInfo],
+    value_enum_ident: &Ident,
+) -> Vec<proc_macro2::TokenStream> {
+    variants
+        .iter()
+        .map(
+            |VariantInfo {
+                 path,
+                 fields,
+                 flattened,
+                 ..
+             }| {
+                let members = fields.iter().map(|field| &field.member);
+                let bindings = (0..fields.len())
+                    .map(|idx| format_ident!("field{}", idx))
+                    .collect::<Vec<_>>();
+                let entries = fields
+                    .iter()
+                    .zip(&bindings)
+                    .map(|(FieldInfo { name, variant, .. }, binding)| {
+                        quote!((#name, #value_enum_ident::#variant(#binding)))
+                    })
+                    .collect::<Vec<_>>();
+                if flattened.is_empty() {
+                    return quote! {
+                        #path { #(#members: #bindings,)* .. } => vec![#(#entries),*]
+                    };
+                }
+                let flatten_members = flattened.iter().map(|flatten| &flatten.member);
+                let flatten_bindings = (0..flattened.len())
+                    .map(|idx| format_ident!("flatten{}", idx))
+                    .collect::<Vec<_>>();
+                let mut stmts = Vec::new();
+                for position in 0..=fields.len() {
+                    for (FlattenInfo { variant, .. }, binding) in flattened
+                        .iter()
+                        .zip(&flatten_bindings)
+                        .filter(|(flatten, _)| flatten.position == position)
+                    {
+                        stmts.push(quote! {
+                            fields.extend(
+                                #binding
+                                    .into_fields()
+                                    .map(|(name, field)| (name, #value_enum_ident::#variant(field))),
+                            );
+                        });
+                    }
+                    if let Some(entry) = entries.get(position) {
+                        stmts.push(quote!(fields.push(#entry);));
+                    }
+                }
+                quote! {
+                    #path { #(#members: #bindings,)* #(#flatten_members: #flatten_bindings,)* .. } => {
+                        let mut fields = Vec::new();
+                        #(#stmts)*
+                        fields
+                    }
+                }
+            },
+        )
+        .collect()
+}
+
+/// Arms of `field_iter_mut` borrowing mutable and iterable fields of the variant at once,
+/// fields of flattened fields are inserted at their positions
+fn generate_iter_mut_arms(
+    variants: &[VariantInfo],
+    value_enum_ident: &Ident,
+) -> Vec<proc_macro2::TokenStream> {
+    variants
+        .iter()
+        .map(
+            |VariantInfo {
+                 path,
+                 fields,
+                 flattened,
+                 ..
+             }| {
+                let mut members = Vec::new();
+                let mut bindings = Vec::new();
+                let entries = fields
+                    .iter()
+                    .map(|FieldInfo { member, name, variant, mutable, iterable, .. }| {
+                        (*mutable && *iterable).then(|| {
+                            let binding = format_ident!("field{}", bindings.len());
+                            let entry = quote!((#name, #value_enum_ident::#variant(#binding)));
+                            members.push(member);
+                            bindings.push(binding);
+                            entry
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let flattened = flattened
+                    .iter()
+                    .filter(|flatten| flatten.mutable && flatten.iterable)
+                    .collect::<Vec<_>>();
+                if flattened.is_empty() {
+                    let entries = entries.iter().flatten();
+                    return quote! {
+                        #path { #(#members: #bindings,)* .. } => vec![#(#entries),*]
+                    };
+                }
+                let flatten_members = flattened.iter().map(|flatten| &flatten.member);
+                let flatten_bindings = (0..flattened.len())
+                    .map(|idx| format_ident!("flatten{}", idx))
+                    .collect::<Vec<_>>();
+                let mut stmts = Vec::new();
+                for position in 0..=fields.len() {
+                    for (FlattenInfo { variant, .. }, binding) in flattened
+                        .iter()
+                        .zip(&flatten_bindings)
+                        .filter(|(flatten, _)| flatten.position == position)
+                    {
+                        stmts.push(quote! {
+                            fields.extend(
+                                #binding
+                                    .field_iter_mut()
+                                    .map(|(name, field)| (name, #value_enum_ident::#variant(field))),
+                            );
+                        });
+                    }
+                    if let Some(Some(entry)) = entries.get(position) {
+                        stmts.push(quote!(fields.push(#entry);));
+                    }
+                }
+                quote! {
+                    #path { #(#members: #bindings,)* #(#flatten_members: #flatten_bindings,)* .. } => {
+                        let mut fields = Vec::new();
+                        #(#stmts)*
+                        fields
+                    }
+                }
+            },
+        )
+        .collect()
+}
+
+/// Arms of `set_field` replacing the field with `value` of the matching variant
+fn generate_set_field_arms(
+    variants: &[VariantInfo],
+    value_enum_ident: &Ident,
+    set_error_ident: &proc_macro2::TokenStream,
+) -> Vec<proc_macro2::TokenStream> {
+    variants
+        .iter()
+        .flat_map(|VariantInfo { path, fields, .. }| {
+            fields.iter().map(move |field| {
+                let FieldInfo {
+                    member,
+                    name,
+                    variant,
+                    ..
+                } = field;
+                let keys = field.keys();
+                if !field.mutable {
+                    return quote! {
+                        (#path { .. }, #keys) => Err(#set_error_ident::ReadOnly(#name))
+                    };
+                }
+                let expected = variant.to_string();
+                quote! {
+                    (#path { #member: field, .. }, #keys) => match value {
+                        #value_enum_ident::#variant(value) => {
+                            Ok(#value_enum_ident::#variant(::std::mem::replace(field, value)))
+                        }
+                        value => Err(#set_error_ident::MismatchedValue {
+                            field: #name,
+                            expected: #expected,
+                            found: value.variant_name(),
+                        }),
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+/// Arms of `set_field_str` parsing `raw` into the field
+fn generate_set_field_str_arms(
+    variants: &[VariantInfo],
+    parse_error_ident: &proc_macro2::TokenStream,
+) -> Vec<proc_macro2::TokenStream> {
+    variants
+        .iter()
+        .flat_map(|VariantInfo { path, fields, .. }| {
+            fields.iter().map(move |field| {
+                let FieldInfo {
+                    member, name, ty, ..
+                } = field;
+                let keys = field.keys();
+                let parsed = match &field.parse {
+                    FieldParse::FromStr => quote!(<#ty as ::std::str::FromStr>::from_str(raw)),
+                    FieldParse::With(parser) => quote!(#parser(raw)),
+                    FieldParse::Disabled => {
+                        return quote! {
+                            (#path { .. }, #keys) => Err(#parse_error_ident::NotParsable(#name))
+                        }
+                    }
+                };
+                quote! {
+                    (#path { #member: field, .. }, #keys) => match #parsed {
+                        Ok(value) => {
+                            *field = value;
+                            Ok(())
+                        }
+                        Err(err) => Err(#parse_error_ident::InvalidValue {
+                            field: #name,
+                            message: err.to_string(),
+                        }),
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+/// Unique pairs of alias and canonical name, aliases cannot clash with other names
+fn collect_aliases<'a>(field_map: &[&'a FieldInfo]) -> syn::Result<Vec<(&'a str, &'a str)>> {
+    let mut alias_map: Vec<(&str, &str)> = Vec::new();
+    for field in field_map {
+        for alias in &field.aliases {
+            let clash = field_map.iter().any(|other| other.name == *alias)
+                || alias_map
+                    .iter()
+                    .any(|(other, name)| other == alias && *name != field.name);
+            if clash {
+                return Err(syn::Error::new(
+                    field.span,
+                    format!(
+                        "Alias `{}` of field `{}` is already used",
+                        alias, field.name
+                    ),
+                ));
+            }
+            if !alias_map.iter().any(|(other, _)| other == alias) {
+                alias_map.push((alias, &field.name));
+            }
+        }
+    }
+    Ok(alias_map)
+}
+
+/// Expression resolving `fieldname` with non-exact lookup mode into the matched name or alias
+/// and its canonical name, `None` for exact lookup
+fn generate_fuzzy_lookup(
+    lookup: LookupMode,
+    field_map: &[&FieldInfo],
+    flatten_tys: &[&Type],
+) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    if lookup == LookupMode::Exact {
+        return Ok(None);
+    }
+    let mut keys: Vec<(String, &str, &str)> = Vec::new();
+    for field in field_map {
+        for key in std::iter::once(&field.name).chain(&field.aliases) {
+            let normalized = lookup.normalize(key);
+            match keys.iter().find(|(other, ..)| *other == normalized) {
+                Some((_, _, name)) if *name != field.name => {
+                    return Err(syn::Error::new(
+                        field.span,
+                        format!(
+                            "Field names `{}` and `{}` are ambiguous with `{}` lookup",
+                            name,
+                            key,
+                            lookup.name()
+                        ),
+                    ))
+                }
+                Some(_) => {}
+                None => keys.push((normalized, key, &field.name)),
+            }
+        }
+    }
+    let (normalized, (keys, names)): (Vec<_>, (Vec<_>, Vec<_>)) = keys
+        .into_iter()
+        .map(|(normalized, key, name)| (normalized, (key, name)))
+        .unzip();
+    // Names and aliases of flattened fields are searched at runtime, the struct checks
+    // in const context that they are unambiguous
+    let flattened_lookup = |key_matches: proc_macro2::TokenStream| {
+        quote! {
+            #(.or_else(|| {
+                <#flatten_tys>::FIELDS
+                    .iter()
+                    .map(|name| (*name, *name))
+                    .chain(<#flatten_tys>::FIELD_ALIASES.iter().copied())
+                    .find(|(key, _)| #key_matches)
+            }))*
+        }
+    };
+    let tokens = match lookup {
+        LookupMode::AsciiCaseInsensitive => {
+            let flattened_lookup = flattened_lookup(quote!(fieldname.eq_ignore_ascii_case(key)));
+            quote! {
+                (#(if fieldname.eq_ignore_ascii_case(#normalized) { Some((#keys, #names)) } else)* { None })
+                    #flattened_lookup
+            }
+        }
+        _ => {
+            let flattened_lookup = flattened_lookup(quote! {
+                normalized.clone().eq(key
+                    .bytes()
+                    .filter(|byte| *byte != b'_' && *byte != b'-')
+                    .map(|byte| byte.to_ascii_lowercase()))
+            });
+            quote! {{
+                let normalized = fieldname
+                    .bytes()
+                    .filter(|byte| *byte != b'_' && *byte != b'-')
+                    .map(|byte| byte.to_ascii_lowercase());
+                (#(if normalized.clone().eq(#normalized.bytes()) { Some((#keys, #names)) } else)* { None })
+                    #flattened_lookup
+            }}
+        }
+    };
+    Ok(Some(tokens))
+}
+
+/// Last arm of lookup by string retrying `call` with canonical name of `fieldname`,
+/// `delegate` statements using `this` may return before `default`
+fn generate_lookup_fallback(
+    fuzzy_lookup: &Option<proc_macro2::TokenStream>,
+    call: proc_macro2::TokenStream,
+    delegate: Option<proc_macro2::TokenStream>,
+    default: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match (fuzzy_lookup, delegate) {
+        (Some(fuzzy_lookup), delegate) => quote! {
+            (this, fieldname) => match #fuzzy_lookup {
+                Some((_, name)) if name != fieldname => this.#call,
+                _ => {
+                    #delegate
+                    #default
+                }
+            }
+        },
+        (None, Some(delegate)) => quote! {
+            (this, fieldname) => {
+                #delegate
+                #default
+            }
+        },
+        (None, None) => quote!(_ => #default),
+    }
+}
+
+/// Enum generated for type of `#[fieldname(nested)]` or `#[fieldname(flatten)]` field,
+/// `config::Tls<T>` with `FieldPath` suffix becomes `config::TlsFieldPath<'field, T>`
+fn generate_derived_type(
+    ty: &Type,
+    suffix: &str,
+    field_lifetime: &syn::GenericParam,
+    option: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (path, args) = derived_type_path(ty, suffix, option)?;
+    let (lifetimes, others): (Vec<_>, Vec<_>) = args
+        .into_iter()
+        .partition(|arg| matches!(arg, syn::GenericArgument::Lifetime(_)));
+    Ok(quote!(#path<#(#lifetimes,)* #field_lifetime #(, #others)*>))
+}
+
+/// Path of type generated for `ty` with `suffix` without generic arguments and the arguments
+fn derived_type_path(
+    ty: &Type,
+    suffix: &str,
+    option: &str,
+) -> syn::Result<(syn::Path, Vec<syn::GenericArgument>)> {
+    let error = || {
+        syn::Error::new_spanned(
+            ty,
+            format!(
+                "`{}` field must have a type deriving FieldnameAccess",
+                option
+            ),
+        )
+    };
+    let path = match ty {
+        Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return Err(error()),
+    };
+    let mut path = path.clone();
+    let last = path.segments.last_mut().expect("path has segments");
+    last.ident = format_ident!("{}{}", last.ident, suffix);
+    let args = match std::mem::replace(&mut last.arguments, syn::PathArguments::None) {
+        syn::PathArguments::None => Vec::new(),
+        syn::PathArguments::AngleBracketed(args) => args.args.into_iter().collect(),
+        syn::PathArguments::Parenthesized(_) => return Err(error()),
+    };
+    Ok((path, args))
+}
+
+/// Segment of field path walking into collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathStep {
+    /// `[3]` segment of `Vec<T>` or `[T; N]`
+    Index,
+    /// `["env"]` segment of `HashMap<String, T>` or `BTreeMap<String, T>`
+    Key,
+    /// `Option<T>` walked without segment
+    Optional,
+}
+
+/// Steps of walking `ty` through collections and type of its elements,
+/// `Option<Vec<Item>>` has steps `[Optional, Index]` and elements of type `Item`
+fn collection_steps(ty: &Type) -> (Vec<PathStep>, &Type) {
+    let mut steps = Vec::new();
+    let mut ty = ty;
+    loop {
+        let (step, elem) = match ty {
+            Type::Array(array) => (PathStep::Index, &*array.elem),
+            Type::Path(syn::TypePath { qself: None, path }) => {
+                let last = path.segments.last().expect("path has segments");
+                let args = match &last.arguments {
+                    syn::PathArguments::AngleBracketed(args) => args
+                        .args
+                        .iter()
+                        .filter_map(|arg| match arg {
+                            syn::GenericArgument::Type(ty) => Some(ty),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                    _ => break,
+                };
+                match (last.ident.to_string().as_str(), args.as_slice()) {
+                    ("Vec", [elem]) => (PathStep::Index, *elem),
+                    ("Option", [elem]) => (PathStep::Optional, *elem),
+                    ("HashMap" | "BTreeMap", [key, elem, ..]) if is_string(key) => {
+                        (PathStep::Key, *elem)
+                    }
+                    _ => break,
+                }
+            }
+            _ => break,
+        };
+        steps.push(step);
+        ty = elem;
+    }
+    (steps, ty)
+}
+
+/// Whether `ty` is `String`
+fn is_string(ty: &Type) -> bool {
+    matches!(ty, Type::Path(syn::TypePath { qself: None, path }) if path.is_ident("String"))
+}
+
+/// Arm of `field_path` or `field_path_mut` walking the rest of path through field
+/// which is a nested struct or a collection
+struct PathArm<'a> {
+    pattern: &'a proc_macro2::TokenStream,
+    name: &'a str,
+    steps: &'a [PathStep],
+    /// Elements are nested structs walked by their `field_path`
+    nested: bool,
+    error_ident: &'a proc_macro2::TokenStream,
+    error_kind_ident: &'a proc_macro2::TokenStream,
+}
+
+impl PathArm<'_> {
+    fn generate(
+        &self,
+        variant: proc_macro2::TokenStream,
+        mutable: bool,
+    ) -> proc_macro2::TokenStream {
+        let Self {
+            pattern,
+            name,
+            steps,
+            nested,
+            error_ident,
+            error_kind_ident,
+        } = self;
+        let (get, as_ref, field_path) = if mutable {
+            (quote!(get_mut), quote!(as_mut), quote!(field_path_mut))
+        } else {
+            (quote!(get), quote!(as_ref), quote!(field_path))
+        };
+        let steps = steps.iter().map(|step| match step {
+            PathStep::Index => quote! {
+                let (segment, next) = #error_ident::split_segment(rest)
+                    .ok_or_else(|| #error_ident::invalid(path, rest))?;
+                let index: usize = segment
+                    .parse()
+                    .map_err(|_| #error_ident::invalid(path, rest))?;
+                let len = value.len();
+                let value = value.#get(index).ok_or_else(|| {
+                    #error_ident::at(path, next, #error_kind_ident::OutOfBounds { index, len })
+                })?;
+                let rest = next;
+            },
+            PathStep::Key => quote! {
+                let (segment, next) = #error_ident::split_segment(rest)
+                    .ok_or_else(|| #error_ident::invalid(path, rest))?;
+                let key = segment
+                    .strip_prefix('"')
+                    .and_then(|key| key.strip_suffix('"'))
+                    .ok_or_else(|| #error_ident::invalid(path, rest))?;
+                let value = value.#get(key).ok_or_else(|| {
+                    #error_ident::at(path, next, #error_kind_ident::MissingKey(key.to_string()))
+                })?;
+                let rest = next;
+            },
+            PathStep::Optional => quote! {
+                let value = value
+                    .#as_ref()
+                    .ok_or_else(|| #error_ident::at(path, rest, #error_kind_ident::NoneValue))?;
+            },
+        });
+        let elem = if *nested {
+            quote! {
+                match rest.strip_prefix('.') {
+                    Some(next) => value
+                        .#field_path(next)
+                        .map(#variant)
+                        .map_err(|err| err.nested(path, next)),
+                    None if rest.is_empty() => {
+                        Err(#error_ident::at(path, rest, #error_kind_ident::IncompletePath))
+                    }
+                    None => Err(#error_ident::unexpected(path, rest)),
+                }
+            }
+        } else {
+            quote! {
+                if rest.is_empty() {
+                    Ok(#variant(value))
+                } else {
+                    Err(#error_ident::unexpected(path, rest))
+                }
+            }
+        };
+        quote! {
+            (#pattern, Some(#name)) => {
+                let value = field;
+                #(#steps)*
+                #elem
+            }
+        }
+    }
+}
+
+/// Variant of generated enum holding type and lifetime parameters which aren't used
+/// by its `variants`, e.g. used only by skipped fields. It cannot be constructed
+fn generate_unused_variant(
+    generics: &Generics,
+    variants: &[proc_macro2::TokenStream],
+) -> Option<proc_macro2::TokenStream> {
+    let tokens = quote!(#(#variants)*);
+    let unused = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                Some((lifetime.ident.to_string(), quote!(&#lifetime ())))
+            }
+            syn::GenericParam::Type(param) => {
+                let ident = &param.ident;
+                Some((ident.to_string(), quote!(#ident)))
+            }
+            syn::GenericParam::Const(_) => None,
+        })
+        .filter(|(name, _)| !mentions_idents(tokens.clone(), std::slice::from_ref(name)))
+        .map(|(_, ty)| quote!(::std::marker::PhantomData<#ty>))
+        .collect::<Vec<_>>();
+    (!unused.is_empty()).then(|| {
+        quote! {
+            #[doc(hidden)]
+            __Unused(::std::convert::Infallible, #(#unused),*)
+        }
+    })
+}
+
+/// Whether `tokens` contain any of `idents`
+fn mentions_idents(tokens: proc_macro2::TokenStream, idents: &[String]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => idents.iter().any(|name| ident == name),
+        proc_macro2::TokenTree::Group(group) => mentions_idents(group.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Const block checking at compile time that fields of flattened fields, only known to the compiler,
+/// don't clash with other fields and aliases or aren't ambiguous with `lookup` mode of the struct.
+/// Checks are spanned on the field they fail for
+fn generate_flatten_check(
+    struct_ident: &Ident,
+    fields: &[FieldInfo],
+    flattened: &[FlattenInfo],
+    lookup: LookupMode,
+) -> proc_macro2::TokenStream {
+    let keys_of = |flatten: &FlattenInfo| {
+        let ty = &flatten.ty;
+        quote!(&<#ty>::FIELDS, &<#ty>::FIELD_ALIASES)
+    };
+    let member_of = |flatten: &FlattenInfo| flatten.member.to_token_stream().to_string();
+    let fuzzy = lookup != LookupMode::Exact;
+    let mut checks = Vec::new();
+    for field in fields {
+        let own_keys = std::iter::once((&field.name, format!("Field `{}`", field.name))).chain(
+            field.aliases.iter().map(|alias| {
+                (
+                    alias,
+                    format!("Alias `{}` of field `{}`", alias, field.name),
+                )
+            }),
+        );
+        for (key, subject) in own_keys {
+            let name = &field.name;
+            for flatten in flattened {
+                let keys = keys_of(flatten);
+                let member = member_of(flatten);
+                let message = format!(
+                    "{} of `{}` clashes with field of flattened `{}`",
+                    subject, struct_ident, member
+                );
+                checks.push(quote_spanned! {field.span=>
+                    if clash(#key, #keys) {
+                        panic!(#message);
+                    }
+                });
+                if fuzzy {
+                    let message = format!(
+                        "{} of `{}` is ambiguous with field of flattened `{}` with `{}` lookup",
+                        subject,
+                        struct_ident,
+                        member,
+                        lookup.name()
+                    );
+                    checks.push(quote_spanned! {field.span=>
+                        if ambiguous(#key, #name, #keys) {
+                            panic!(#message);
+                        }
+                    });
+                }
+            }
+        }
+    }
+    for (idx, flatten) in flattened.iter().enumerate() {
+        let keys = keys_of(flatten);
+        let member = member_of(flatten);
+        if fuzzy {
+            let message = format!(
+                "Fields of flattened `{}` of `{}` are ambiguous with `{}` lookup",
+                member,
+                struct_ident,
+                lookup.name()
+            );
+            checks.push(quote_spanned! {flatten.span=>
+                if ambiguous_within(#keys) {
+                    panic!(#message);
+                }
+            });
+        }
+        for other in &flattened[..idx] {
+            let other_keys = keys_of(other);
+            let message = format!(
+                "Fields of flattened `{}` and `{}` of `{}` clash",
+                member_of(other),
+                member,
+                struct_ident
+            );
+            checks.push(quote_spanned! {flatten.span=>
+                if clash_between(#other_keys, #keys, false) {
+                    panic!(#message);
+                }
+            });
+            if fuzzy {
+                let message = format!(
+                    "Fields of flattened `{}` and `{}` of `{}` are ambiguous with `{}` lookup",
+                    member_of(other),
+                    member,
+                    struct_ident,
+                    lookup.name()
+                );
+                checks.push(quote_spanned! {flatten.span=>
+                    if clash_between(#other_keys, #keys, true) {
+                        panic!(#message);
+                    }
+                });
+            }
+        }
+    }
+    let skipped = match lookup {
+        LookupMode::Normalized => quote!(byte == b'_' || byte == b'-'),
+        _ => quote!(false),
+    };
+    let lookup_eq = if !fuzzy {
+        quote!(str_eq(left, right))
+    } else {
+        quote! {
+            let (left, right) = (left.as_bytes(), right.as_bytes());
+            let (mut left_idx, mut right_idx) = (0, 0);
+            loop {
+                while left_idx < left.len() && skipped(left[left_idx]) {
+                    left_idx += 1;
+                }
+                while right_idx < right.len() && skipped(right[right_idx]) {
+                    right_idx += 1;
+                }
+                if left_idx == left.len() || right_idx == right.len() {
+                    return left_idx == left.len() && right_idx == right.len();
+                }
+                if !left[left_idx].eq_ignore_ascii_case(&right[right_idx]) {
+                    return false;
+                }
+                left_idx += 1;
+                right_idx += 1;
+            }
+        }
+    };
+    quote! {
+        #[allow(dead_code)]
+        const _: () = {
+            const fn str_eq(left: &str, right: &str) -> bool {
+                let (left, right) = (left.as_bytes(), right.as_bytes());
+                if left.len() != right.len() {
+                    return false;
+                }
+                let mut idx = 0;
+                while idx < left.len() {
+                    if left[idx] != right[idx] {
+                        return false;
+                    }
+                    idx += 1;
+                }
+                true
+            }
+            const fn skipped(byte: u8) -> bool {
+                #skipped
+            }
+            const fn lookup_eq(left: &str, right: &str) -> bool {
+                #lookup_eq
+            }
+            // Name or alias at `idx` of flattened type and the name it resolves into
+            const fn key_at<'a>(
+                fields: &[&'a str],
+                aliases: &[(&'a str, &'a str)],
+                idx: usize,
+            ) -> (&'a str, &'a str) {
+                if idx < fields.len() {
+                    (fields[idx], fields[idx])
+                } else {
+                    aliases[idx - fields.len()]
+                }
+            }
+            const fn clash(key: &str, fields: &[&str], aliases: &[(&str, &str)]) -> bool {
+                let mut idx = 0;
+                while idx < fields.len() + aliases.len() {
+                    if str_eq(key, key_at(fields, aliases, idx).0) {
+                        return true;
+                    }
+                    idx += 1;
+                }
+                false
+            }
+            // Key is matched by lookup with key resolving into another name
+            const fn ambiguous(
+                key: &str,
+                name: &str,
+                fields: &[&str],
+                aliases: &[(&str, &str)],
+            ) -> bool {
+                let mut idx = 0;
+                while idx < fields.len() + aliases.len() {
+                    let (other_key, other_name) = key_at(fields, aliases, idx);
+                    if lookup_eq(key, other_key) && !str_eq(name, other_name) {
+                        return true;
+                    }
+                    idx += 1;
+                }
+                false
+            }
+            const fn ambiguous_within(fields: &[&str], aliases: &[(&str, &str)]) -> bool {
+                let mut idx = 0;
+                while idx < fields.len() + aliases.len() {
+                    let (key, name) = key_at(fields, aliases, idx);
+                    if ambiguous(key, name, fields, aliases) {
+                        return true;
+                    }
+                    idx += 1;
+                }
+                false
+            }
+            const fn clash_between(
+                fields: &[&str],
+                aliases: &[(&str, &str)],
+                other_fields: &[&str],
+                other_aliases: &[(&str, &str)],
+                fuzzy: bool,
+            ) -> bool {
+                let mut idx = 0;
+                while idx < fields.len() + aliases.len() {
+                    let (key, name) = key_at(fields, aliases, idx);
+                    let found = if fuzzy {
+                        ambiguous(key, name, other_fields, other_aliases)
+                    } else {
+                        clash(key, other_fields, other_aliases)
+                    };
+                    if found {
+                        return true;
+                    }
+                    idx += 1;
+                }
+                false
+            }
+            #(#checks)*
+        };
+    }
+}
+
+/// Expression building list of field names with fields of flattened fields inserted
+/// at their positions and its length, `list` is `FIELDS` or `ITER_FIELDS`
+fn generate_flatten_list(
+    fields: &[FieldInfo],
+    flattened: &[FlattenInfo],
+    list: &Ident,
+    iterable_only: bool,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let mut len = quote!(0);
+    let mut stmts = Vec::new();
+    for position in 0..=fields.len() {
+        for FlattenInfo { ty, .. } in flattened
+            .iter()
+            .filter(|flatten| flatten.position == position && (flatten.iterable || !iterable_only))
+        {
+            stmts.push(quote! {
+                let mut idx = 0;
+                while idx < <#ty>::#list.len() {
+                    fields[#len + idx] = <#ty>::#list[idx];
+                    idx += 1;
+                }
+            });
+            len = quote!(#len + <#ty>::#list.len());
+        }
+        if let Some(FieldInfo { name, .. }) = fields
+            .get(position)
+            .filter(|field| field.iterable || !iterable_only)
+        {
+            stmts.push(quote!(fields[#len] = #name;));
+            len = quote!(#len + 1);
+        }
+    }
+    let builder = quote! {{
+        let mut fields = [""; #len];
+        #(#stmts)*
+        fields
+    }};
+    (builder, len)
+}
+
+/// `FIELD_ALIASES` builder and its length with aliases of the struct followed by aliases
+/// of its flattened fields
+fn generate_flatten_aliases(
+    alias_entries: &[proc_macro2::TokenStream],
+    flatten_tys: &[&Type],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let own_len = alias_entries.len();
+    let own_idx = 0..own_len;
+    let mut len = quote!(#own_len);
+    let mut stmts = Vec::new();
+    for ty in flatten_tys {
+        stmts.push(quote! {
+            let mut idx = 0;
+            while idx < <#ty>::FIELD_ALIASES.len() {
+                aliases[#len + idx] = <#ty>::FIELD_ALIASES[idx];
+                idx += 1;
+            }
+        });
+        len = quote!(#len + <#ty>::FIELD_ALIASES.len());
+    }
+    let builder = quote! {{
+        let mut aliases = [("", ""); #len];
+        #(aliases[#own_idx] = #alias_entries;)*
+        #(#stmts)*
+        aliases
+    }};
+    (builder, len)
+}
+
+/// Items of `items` aligned with `field_map` which belong to mutable fields
+fn filter_mutable<'a, T>(items: &'a [T], field_map: &[&FieldInfo]) -> Vec<&'a T> {
+    items
+        .iter()
+        .zip(field_map)
+        .filter(|(_, field)| field.mutable)
+        .map(|(item, _)| item)
+        .collect()
+}
+
+fn generate_field_refs(
+    field_map: &[&FieldInfo],
+    value_enum_ident: &Ident,
+) -> Vec<proc_macro2::TokenStream> {
+    field_map
+        .iter()
+        .map(|FieldInfo { variant, .. }| {
+            quote! {
+                #value_enum_ident::#variant(field)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,289 @@
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+
+/// Error of getting field by its name with suggestions of similar names, returned by `try_field`
+/// and `try_field_mut`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownField {
+    /// Requested field name
+    pub requested: String,
+    /// Name of the type or enum variant the field was requested on
+    pub type_name: &'static str,
+    /// Valid field names
+    pub valid: &'static [&'static str],
+    /// Valid field names nearest to the requested one by edit distance
+    pub suggestions: Vec<&'static str>,
+}
+
+impl UnknownField {
+    /// Creates error suggesting names from `valid` nearest to `requested`
+    pub fn new(requested: &str, type_name: &'static str, valid: &'static [&'static str]) -> Self {
+        let distances = valid
+            .iter()
+            .map(|name| (*name, distance(requested, name)))
+            .collect::<Vec<_>>();
+        let max_distance = cmp::max(1, requested.chars().count() / 3);
+        let suggestions = match distances.iter().map(|(_, distance)| *distance).min() {
+            Some(nearest) if nearest <= max_distance => distances
+                .into_iter()
+                .filter(|(_, distance)| *distance == nearest)
+                .map(|(name, _)| name)
+                .collect(),
+            _ => Vec::new(),
+        };
+        Self {
+            requested: requested.to_string(),
+            type_name,
+            valid,
+            suggestions,
+        }
+    }
+}
+
+/// Optimal string alignment distance between two strings, Levenshtein distance which also counts
+/// swap of adjacent characters as a single edit
+fn distance(from: &str, to: &str) -> usize {
+    let (from, to) = (
+        from.chars().collect::<Vec<_>>(),
+        to.chars().collect::<Vec<_>>(),
+    );
+    let mut rows = vec![(0..=to.len()).collect::<Vec<_>>()];
+    for i in 1..=from.len() {
+        let mut row = vec![i; to.len() + 1];
+        for j in 1..=to.len() {
+            let previous = &rows[i - 1];
+            let substitution = previous[j - 1] + usize::from(from[i - 1] != to[j - 1]);
+            row[j] = cmp::min(substitution, cmp::min(previous[j], row[j - 1]) + 1);
+            if i > 1 && j > 1 && from[i - 1] == to[j - 2] && from[i - 2] == to[j - 1] {
+                row[j] = cmp::min(row[j], rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[from.len()][to.len()]
+}
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown field {:?} on {}",
+            self.requested, self.type_name
+        )?;
+        for (idx, suggestion) in self.suggestions.iter().enumerate() {
+            let prefix = match idx {
+                0 => ", did you mean ",
+                _ if idx + 1 == self.suggestions.len() => " or ",
+                _ => ", ",
+            };
+            write!(f, "{}{:?}", prefix, suggestion)?;
+        }
+        if !self.suggestions.is_empty() {
+            f.write_str("?")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for UnknownField {}
+
+/// Error of setting field by its name, returned by `set_field`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetFieldError {
+    /// There is no field with such name
+    UnknownField(String),
+    /// Variant of the value doesn't match variant of the field
+    MismatchedValue {
+        field: &'static str,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// Field is marked with `#[fieldname(skip_mut)]`
+    ReadOnly(&'static str),
+}
+
+impl fmt::Display for SetFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField(field) => write!(f, "unknown field `{}`", field),
+            Self::MismatchedValue {
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "field `{}` expects `{}` value, found `{}`",
+                field, expected, found
+            ),
+            Self::ReadOnly(field) => write!(f, "field `{}` is read-only", field),
+        }
+    }
+}
+
+impl Error for SetFieldError {}
+
+/// Error of setting field by its name from string, returned by `set_field_str`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseFieldError {
+    /// There is no field with such name
+    UnknownField(String),
+    /// Field is marked with `#[fieldname(no_parse)]` or `#[fieldname(skip_mut)]`, or it is a field
+    /// of `#[fieldname(flatten)]` field
+    NotParsable(&'static str),
+    /// String cannot be parsed into the field type
+    InvalidValue {
+        field: &'static str,
+        message: String,
+    },
+}
+
+impl fmt::Display for ParseFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField(field) => write!(f, "unknown field `{}`", field),
+            Self::NotParsable(field) => write!(f, "field `{}` cannot be set from string", field),
+            Self::InvalidValue { field, message } => {
+                write!(f, "invalid value for field `{}`: {}", field, message)
+            }
+        }
+    }
+}
+
+impl Error for ParseFieldError {}
+
+/// Error of converting into `<Struct>FieldName`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldNameError {
+    /// There is no field with such name
+    UnknownName(String),
+    /// There is no field with such position
+    OutOfRange(usize),
+}
+
+impl fmt::Display for FieldNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownName(field) => write!(f, "unknown field `{}`", field),
+            Self::OutOfRange(idx) => write!(f, "there is no field at position {}", idx),
+        }
+    }
+}
+
+impl Error for FieldNameError {}
+
+/// Error of getting field by path, returned by `field_path` and `field_path_mut`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPathError {
+    /// Part of the path up to the segment which failed, whole path for `InvalidSyntax`
+    pub path: String,
+    /// Reason of the failure
+    pub kind: FieldPathErrorKind,
+}
+
+/// Reason of failure of getting field by path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldPathErrorKind {
+    /// Path cannot be parsed at byte `position`
+    InvalidSyntax { position: usize },
+    /// There is no field with such name
+    UnknownField,
+    /// Index is out of bounds of collection with length `len`
+    OutOfBounds { index: usize, len: usize },
+    /// Map has no value with such key
+    MissingKey(String),
+    /// Optional value is `None`
+    NoneValue,
+    /// Value has neither fields nor elements
+    NotNavigable,
+    /// Path ends at a struct instead of its field
+    IncompletePath,
+}
+
+impl FieldPathError {
+    /// Error of `kind` at the part of `path` before `rest`
+    #[doc(hidden)]
+    pub fn at(path: &str, rest: &str, kind: FieldPathErrorKind) -> Self {
+        Self {
+            path: path[..path.len() - rest.len()].to_string(),
+            kind,
+        }
+    }
+
+    /// Error of unexpected `rest` of `path`
+    #[doc(hidden)]
+    pub fn unexpected(path: &str, rest: &str) -> Self {
+        if rest.starts_with(['.', '[']) {
+            Self::at(path, rest, FieldPathErrorKind::NotNavigable)
+        } else {
+            Self::invalid(path, rest)
+        }
+    }
+
+    /// Error of `path` which cannot be parsed at the start of `rest`
+    #[doc(hidden)]
+    pub fn invalid(path: &str, rest: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            kind: FieldPathErrorKind::InvalidSyntax {
+                position: path.len() - rest.len(),
+            },
+        }
+    }
+
+    /// Error of nested field at `rest` of `path` with path relative to the nested field
+    #[doc(hidden)]
+    pub fn nested(self, path: &str, rest: &str) -> Self {
+        let start = path.len() - rest.len();
+        Self {
+            path: format!("{}{}", &path[..start], self.path),
+            kind: match self.kind {
+                FieldPathErrorKind::InvalidSyntax { position } => {
+                    FieldPathErrorKind::InvalidSyntax {
+                        position: start + position,
+                    }
+                }
+                kind => kind,
+            },
+        }
+    }
+
+    /// Splits `[..]` segment off the start of `rest` returning its content and the rest
+    #[doc(hidden)]
+    pub fn split_segment(rest: &str) -> Option<(&str, &str)> {
+        let rest = rest.strip_prefix('[')?;
+        let end = match rest.strip_prefix('"') {
+            Some(key) => key.find('"')? + 2,
+            None => rest.find(']')?,
+        };
+        rest[end..]
+            .strip_prefix(']')
+            .map(|next| (&rest[..end], next))
+    }
+}
+
+impl fmt::Display for FieldPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            FieldPathErrorKind::InvalidSyntax { position } => {
+                write!(f, "invalid path `{}` at position {}", self.path, position)
+            }
+            FieldPathErrorKind::UnknownField => write!(f, "unknown field `{}`", self.path),
+            FieldPathErrorKind::OutOfBounds { index, len } => write!(
+                f,
+                "index {} of `{}` is out of bounds for length {}",
+                index, self.path, len
+            ),
+            FieldPathErrorKind::MissingKey(key) => {
+                write!(f, "key {:?} of `{}` is missing", key, self.path)
+            }
+            FieldPathErrorKind::NoneValue => write!(f, "`{}` is None", self.path),
+            FieldPathErrorKind::NotNavigable => {
+                write!(f, "`{}` has no fields or elements", self.path)
+            }
+            FieldPathErrorKind::IncompletePath => write!(f, "`{}` is not a field", self.path),
+        }
+    }
+}
+
+impl Error for FieldPathError {}
@@ -1,18 +1,153 @@
-use itertools::Itertools;
-use proc_macro::TokenStream;
-use proc_macro2::{Ident, Span, TokenTree};
-use quote::{quote, ToTokens};
-use syn::{
-    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Expr, ExprLit, Fields,
-    FieldsNamed, Generics, Lit, Meta, Type, TypeGenerics, Visibility, WhereClause,
-};
-
 /// # Description
 ///
 /// Derive macro for safe struct field access by their names in runtime.
 ///
-/// Also it generates `const FIELDS: [&'static str; FIELDS_COUNT]` constant with struct fields and `field_iter`
-/// method on struct for creating `Iterator` over struct using generated field enum.
+/// Also it generates `const FIELDS: [&'static str; FIELD_COUNT]` and `const FIELD_COUNT: usize`
+/// constants with struct fields, `field_at` method for access by field position and `field_iter`
+/// method on struct for creating `Iterator` over struct using generated field enum. `field_iter_mut`
+/// yields mutable references to all fields at once for bulk edits. Iterator of `field_iter` gets fields
+/// by position and implements `ExactSizeIterator`, `DoubleEndedIterator`, `FusedIterator` and `Clone`.
+/// Constants have the same visibility as the struct.
+///
+/// Generic types are supported with bounds, defaults, const generics and where clauses. Generated
+/// enums which don't use some type or lifetime parameter, e.g. used only by skipped fields, get hidden
+/// uninhabited `__Unused` variant holding it, so matching them needs a wildcard arm.
+///
+/// Fields can be moved out with `into_field` and `into_fields` methods returning `<Name>FieldValue`
+/// enum with owned values. The methods are generated with `#fieldname_enum(into_fields)`, as types
+/// implementing `Drop` cannot be moved out this way.
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess)]
+/// #[fieldname_enum(into_fields)]
+/// struct Message {
+///     topic: String,
+///     partition: u32,
+/// }
+///
+/// let message = Message { topic: String::from("users"), partition: 3 };
+/// for (name, value) in message.into_fields() {
+///     match value {
+///         MessageFieldValue::String(topic) => assert_eq!((name, topic.as_str()), ("topic", "users")),
+///         MessageFieldValue::U32(partition) => assert_eq!((name, partition), ("partition", 3)),
+///     }
+/// }
+///```
+///
+/// The same enum is used by `set_field` method which replaces field with value of the matching variant
+/// and returns the old one. `SetFieldError` tells whether there is no such field or variant of the value
+/// doesn't match variant of the field.
+///
+///```rust
+/// use fieldname_access::{FieldnameAccess, SetFieldError};
+///
+/// #[derive(FieldnameAccess)]
+/// struct Message {
+///     topic: String,
+///     partition: u32,
+/// }
+///
+/// let mut message = Message { topic: String::from("users"), partition: 3 };
+/// assert!(matches!(message.set_field("partition", MessageFieldValue::U32(7)), Ok(MessageFieldValue::U32(3))));
+/// assert_eq!(
+///     message.set_field("topic", MessageFieldValue::U32(7)).err(),
+///     Some(SetFieldError::MismatchedValue { field: "topic", expected: "String", found: "U32" })
+/// );
+///```
+///
+/// Field names are also available as `<Struct>FieldName` enum with unit variant for every field.
+/// It has `ALL` constant, `as_str` and `index` methods and implements `Display`, `FromStr` and `TryFrom<usize>`.
+/// `field_by_name` and `field_by_name_mut` methods take it instead of string and never fail for structs
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess)]
+/// struct User {
+///     name: String,
+///     date_of_birth: u64,
+/// }
+///
+/// let user = User { name: String::from("Ranni"), date_of_birth: 0 };
+/// let fieldname: UserFieldName = "date_of_birth".parse().unwrap();
+/// assert_eq!(fieldname, UserFieldName::DateOfBirth);
+/// assert_eq!(UserFieldName::ALL[fieldname.index()].to_string(), "date_of_birth");
+/// match user.field_by_name(UserFieldName::Name) {
+///     UserField::String(name) => assert_eq!(name, "Ranni"),
+///     UserField::U64(_) => unreachable!(),
+/// }
+///```
+///
+/// `try_field` and `try_field_mut` methods return `UnknownField` error instead of `None`.
+/// It has requested name, name of the type, valid field names and the nearest of them by edit distance,
+/// where swapped adjacent letters count as a single edit
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess)]
+/// struct User {
+///     name: String,
+///     age: u64,
+/// }
+///
+/// let user = User { name: String::from("Ranni"), age: 1 };
+/// let err = user.try_field("agee").err().unwrap();
+/// assert_eq!(err.suggestions, ["age"]);
+/// assert_eq!(err.to_string(), "unknown field \"agee\" on User, did you mean \"age\"?");
+///```
+///
+/// When the field is known at compile time, `get` and `get_mut` methods of `FieldnameAccessExt` trait take
+/// zero-sized marker type of the field from module generated with `#fieldname_enum(markers)`,
+/// e.g. `user_fields::Age`. Markers are as visible as their fields. Markers
+/// implement `Field<User>` trait with `Value` type of the field and `NAME` of the field, and `FieldMut<User>`
+/// unless field is `#[fieldname(skip_mut)]`, so code can be generic over fields of some type.
+/// Flattened fields don't have markers
+///
+///```rust
+/// use fieldname_access::{Field, FieldnameAccess, FieldnameAccessExt};
+///
+/// #[derive(FieldnameAccess)]
+/// #[fieldname_enum(markers)]
+/// struct User {
+///     name: String,
+///     age: u64,
+///     visits: u64,
+/// }
+///
+/// fn total<T, F: Field<T, Value = u64>>(items: &[T]) -> u64 {
+///     items.iter().map(|item| *F::get(item)).sum()
+/// }
+///
+/// let mut user = User { name: String::from("Ranni"), age: 1, visits: 3 };
+/// *user.get_mut::<user_fields::Age>() += 1;
+/// assert_eq!(user.get::<user_fields::Name>(), "Ranni");
+/// assert_eq!(total::<_, user_fields::Age>(&[user]), 2);
+/// assert_eq!(<user_fields::Visits as Field<User>>::NAME, "visits");
+///```
+///
+/// Derived types also implement `FieldnameAccess` trait with `Field`, `FieldMut` and `FieldIter`
+/// associated types, `FIELDS` constant and `field`, `field_mut` and `field_iter` methods, so code can
+/// be generic over them
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess)]
+/// struct User {
+///     name: String,
+///     email: Option<String>,
+/// }
+///
+/// fn audit<T: FieldnameAccess>(item: &T) -> Vec<&'static str> {
+///     item.field_iter().map(|(name, _)| name).collect()
+/// }
+///
+/// let user = User { name: String::from("Ranni"), email: None };
+/// assert_eq!(audit(&user), <User as FieldnameAccess>::FIELDS);
+///```
 ///
 ///### Container attributes
 ///* `#fieldname_enum(name = "NewName")` - Name of generated enum of possible values
@@ -38,9 +173,10 @@ use syn::{
 /// }
 ///```
 ///
-///* `#fieldname_enum(derive = [Debug, Clone], derive_mut = [Debug])` - Derive macroses for generated enums.
-/// `derive` only for enum with immutable references, `derive_mut` only for enum with mutable references.
-/// It can be helpful when you want to derive `Clone` but only for immutable references as mutable are not clonable
+///* `#fieldname_enum(derive = [Debug, Clone], derive_mut = [Debug], derive_value = [Debug, Clone])` - Derive macroses for generated enums.
+///  `derive` only for enum with immutable references, `derive_mut` only for enum with mutable references,
+///  `derive_value` only for enum with owned values.
+///  It can be helpful when you want to derive `Clone` but only for immutable references as mutable are not clonable
 ///
 ///```rust
 /// use fieldname_access::FieldnameAccess;
@@ -53,7 +189,7 @@ use syn::{
 /// }
 ///```
 ///
-///* `#fieldname_enum(derive_all = [Debug])` - Derive macroses for immutable and mutable generated enums
+///* `#fieldname_enum(derive_all = [Debug])` - Derive macroses for all generated enums
 ///
 ///```rust
 /// use fieldname_access::FieldnameAccess;
@@ -66,10 +202,171 @@ use syn::{
 /// }
 ///```
 ///
+///* `#fieldname_enum(parse)` - Generate `set_field_str` method setting field by its name from string
+///  using `FromStr` of the field type. Fields without `FromStr` have to be marked with
+///  `#fieldname(no_parse)` or `#fieldname(parse_with = "path::to::fn")`
+///
+///```rust
+/// use fieldname_access::{FieldnameAccess, ParseFieldError};
+///
+/// fn parse_tags(raw: &str) -> Result<Vec<String>, std::convert::Infallible> {
+///     Ok(raw.split(',').map(String::from).collect())
+/// }
+///
+/// #[derive(FieldnameAccess, Default)]
+/// #[fieldname_enum(parse)]
+/// struct User {
+///     age: u64,
+///     #[fieldname(parse_with = "parse_tags")]
+///     tags: Vec<String>,
+///     #[fieldname(no_parse)]
+///     avatar: Vec<u8>,
+/// }
+///
+/// let mut user = User::default();
+/// user.set_field_str("age", "18").unwrap();
+/// user.set_field_str("tags", "admin,owner").unwrap();
+/// assert_eq!(user.age, 18);
+/// assert_eq!(
+///     user.set_field_str("age", "old").unwrap_err().to_string(),
+///     "invalid value for field `age`: invalid digit found in string"
+/// );
+/// assert_eq!(user.set_field_str("avatar", ""), Err(ParseFieldError::NotParsable("avatar")));
+///```
+///
+///* `#fieldname_enum(fields_vis = "pub")` - Visibility of `FIELDS` and `FIELD_COUNT` constants
+///  if it should differ from the struct one
+///
+///```rust
+/// mod model {
+///     use fieldname_access::FieldnameAccess;
+///
+///     #[derive(FieldnameAccess)]
+///     #[fieldname_enum(fields_vis = "pub")]
+///     pub(crate) struct Row {
+///         id: u64,
+///         title: String,
+///     }
+/// }
+///
+/// let header: [&str; model::Row::FIELD_COUNT] = model::Row::FIELDS;
+/// assert_eq!(header, ["id", "title"]);
+///```
+///
+///* `#fieldname_enum(rename_all = "camelCase")` - Naming convention of field names used for access,
+///  in `FIELDS` and `field_iter`. Supports the same conventions as serde: `lowercase`, `UPPERCASE`,
+///  `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` and `SCREAMING-KEBAB-CASE`.
+///  Tuple fields are not affected
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess, Default)]
+/// #[fieldname_enum(rename_all = "camelCase")]
+/// struct User {
+///     date_of_birth: u64,
+///     r#type: u8,
+/// }
+///
+/// let user = User::default();
+/// assert_eq!(User::FIELDS, ["dateOfBirth", "type"]);
+/// assert!(user.field("dateOfBirth").is_some());
+/// assert!(user.field("date_of_birth").is_none());
+///```
+///
+///* `#fieldname_enum(lookup = "ascii_case_insensitive")` - How lookups by string match field names
+///  and aliases when there is no exact match. `ascii_case_insensitive` ignores case and `normalized`
+///  ignores case, `_` and `-`. Names which become equal this way are rejected at compile time
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess, Default)]
+/// #[fieldname_enum(lookup = "normalized")]
+/// struct User {
+///     user_name: String,
+/// }
+///
+/// let user = User::default();
+/// assert!(user.field("User-Name").is_some());
+/// assert!(user.field("USERNAME").is_some());
+/// assert_eq!(User::canonical_name("userName"), Some("user_name"));
+///```
+///
+///* `#fieldname_enum(variants = "per_field")` - Every field gets its own variant named after the field
+///  in PascalCase instead of sharing one with fields of the same type, so matches over fields are checked
+///  for exhaustiveness by the compiler. `per_type` is the default
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess, Default)]
+/// #[fieldname_enum(variants = "per_field")]
+/// struct User {
+///     name: String,
+///     email: String,
+/// }
+///
+/// let user = User::default();
+/// for (_, field) in user.field_iter() {
+///     match field {
+///         UserField::Name(name) => assert!(name.is_empty()),
+///         UserField::Email(email) => assert!(email.is_empty()),
+///     }
+/// }
+///```
+///
+///* `#fieldname_enum(markers)` - Generate module `<struct>_fields` in `snake_case` with marker types of
+///  fields for `FieldnameAccessExt`. Its name can be set with `#fieldname_enum(markers = "user_markers")`.
+///  Only structs have markers
+///
+///```rust
+/// use fieldname_access::{FieldnameAccess, FieldnameAccessExt};
+///
+/// #[derive(FieldnameAccess, Default)]
+/// #[fieldname_enum(markers = "user_markers")]
+/// struct User {
+///     age: u64,
+/// }
+///
+/// impl User {
+///     fn get(&self) -> u64 {
+///         self.age
+///     }
+/// }
+///
+/// let user = User { age: 18 };
+/// assert_eq!(user.get(), 18);
+/// assert_eq!(FieldnameAccessExt::get::<user_markers::Age>(&user), &18);
+///```
+///
+///* `#fieldname_enum(into_fields)` - Generate `into_field` and `into_fields` methods moving fields out of
+///  the value. Types implementing `Drop` cannot use it
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess)]
+/// #[fieldname_enum(into_fields)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let user = User { name: String::from("Ranni") };
+/// assert!(matches!(user.into_field("name"), Some(UserFieldValue::String(name)) if name == "Ranni"));
+///```
+///
 ///### Field attributes
 ///
 ///* `#fieldname = "AmazingAge"` - Name of variant for field in generated enum.
-///It can be helpfull when you want to 'mark' field with specific variant name
+///  It can be helpfull when you want to 'mark' field with specific variant name.
+///  By default variants are named after the field type: `u8` becomes `U8`, `Option<String>` becomes
+///  `OptionString`, `&'a str` - `RefStr`, `&mut T` - `RefMutT`, `[u8; 4]` - `Array4U8`, `&[T]` - `RefSliceT`,
+///  `(u8, bool)` - `TupleU8Bool`, `()` - `Unit`, `*const T` - `ConstPtrT`, `fn(u8) -> bool` - `FnU8ToBool`
+///  and `Box<dyn Display>` - `BoxDynDisplay`. Different types getting the same name like `a::Id` and `b::Id`
+///  are qualified with segments of their paths as `AId` and `BId`, types differing only in lifetimes
+///  like `&'a str` and `&'b str` get their names as `RefAStr` and `RefBStr`. Fields of different types
+///  with the same variant are compile errors
 ///
 ///```rust
 /// use fieldname_access::FieldnameAccess;
@@ -91,297 +388,276 @@ use syn::{
 ///     NamedFieldnameFieldMut::String(val) => {}
 ///     NamedFieldnameFieldMut::MyAge(val) => {}
 ///     NamedFieldnameFieldMut::I64(val) => {}
-/// }  
+/// }
 ///```
+///
+///* `#fieldname(name = "r")` - Name of tuple struct field used for access and in `FIELDS`.
+///  Tuple struct fields are named by their index by default
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess)]
+/// struct Rgb(#[fieldname(name = "r")] u8, u8, u8);
+///
+/// let color = Rgb(1, 2, 3);
+/// assert_eq!(Rgb::FIELDS, ["r", "1", "2"]);
+/// match color.field("r").unwrap() {
+///     RgbField::U8(val) => assert_eq!(*val, 1),
+/// }
+/// match color.field_at(2).unwrap() {
+///     RgbField::U8(val) => assert_eq!(*val, 3),
+/// }
+///```
+///
+///* `#fieldname(rename = "dateOfBirth")` - Name of field used for access, in `FIELDS` and `field_iter`.
+///  It takes precedence over `rename_all`
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess, Default)]
+/// #[fieldname_enum(rename_all = "kebab-case")]
+/// struct User {
+///     #[fieldname(rename = "dateOfBirth")]
+///     date_of_birth: u64,
+///     user_name: String,
+/// }
+///
+/// assert_eq!(User::FIELDS, ["dateOfBirth", "user-name"]);
+///```
+///
+///* `#fieldname(alias = "old_name")` - Deprecated name of field also accepted by lookups by string.
+///  It can be repeated. `FIELDS` and `field_iter` list only the canonical name, `canonical_name`
+///  resolves alias into it and `is_deprecated_alias` tells whether the name is an alias, both using `lookup` mode
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess, Default)]
+/// struct User {
+///     #[fieldname(alias = "username", alias = "login")]
+///     name: String,
+/// }
+///
+/// let user = User::default();
+/// assert_eq!(User::FIELDS, ["name"]);
+/// assert!(user.field("login").is_some());
+/// assert_eq!(User::canonical_name("username"), Some("name"));
+/// assert!(User::is_deprecated_alias("login"));
+/// assert!(!User::is_deprecated_alias("name"));
+///```
+///
+///* `#fieldname(nested)` - Field of type also deriving `FieldnameAccess` which fields are accessible
+///  by path with `field_path` and `field_path_mut`. They return `<Struct>FieldPath` enum with
+///  `Field` variant for field of the struct itself and variant wrapping path enum of nested type for
+///  every nested field. `field_paths` lists paths to all fields of the struct and nested structs.
+///  Paths also walk into `Vec<T>` and `[T; N]` by index, `HashMap<String, T>` and `BTreeMap<String, T>`
+///  by key and through `Option<T>`, like `items[3].price` or `labels["env"]`. Elements are walked
+///  further if the field is nested, otherwise path enums have variant with reference to element.
+///  Failures are reported by `FieldPathError` with the failed part of the path and its reason.
+///  Variant of nested field or collection named `field` is `Field_`, as `Field` is already taken
+///
+///```rust
+/// use std::collections::HashMap;
+/// use fieldname_access::{FieldPathErrorKind, FieldnameAccess};
+///
+/// #[derive(FieldnameAccess, Default)]
+/// struct Tls {
+///     cert_path: String,
+/// }
+///
+/// #[derive(FieldnameAccess, Default)]
+/// struct Server {
+///     port: u16,
+///     #[fieldname(nested)]
+///     tls: Tls,
+///     #[fieldname(nested)]
+///     replicas: Vec<Tls>,
+///     labels: HashMap<String, String>,
+/// }
+///
+/// let mut server = Server::default();
+/// assert_eq!(
+///     Server::field_paths(),
+///     ["port", "tls.cert_path", "replicas[].cert_path", "labels"]
+/// );
+/// if let Ok(ServerFieldPathMut::Tls(TlsFieldPathMut::Field(TlsFieldMut::String(path)))) =
+///     server.field_path_mut("tls.cert_path")
+/// {
+///     *path = String::from("/etc/cert.pem");
+/// }
+/// assert_eq!(server.tls.cert_path, "/etc/cert.pem");
+///
+/// server.labels.insert(String::from("env"), String::from("prod"));
+/// assert!(matches!(
+///     server.field_path(r#"labels["env"]"#),
+///     Ok(ServerFieldPath::Labels(env)) if env == "prod"
+/// ));
+/// let err = server.field_path("replicas[0].cert_path").err().unwrap();
+/// assert_eq!(err.path, "replicas[0]");
+/// assert_eq!(err.kind, FieldPathErrorKind::OutOfBounds { index: 0, len: 0 });
+///```
+///
+///* `#fieldname(flatten)` - Field of struct type also deriving `FieldnameAccess` which fields are
+///  accessible by `field`, `field_mut`, `field_at`, `field_iter` and `set_field` and listed in `FIELDS`
+///  and `field_paths` as if they were declared in the struct. With `#fieldname_enum(into_fields)` they
+///  are moved out by `into_field` and `into_fields`, which the flattened type has to generate too.
+///  Generated enums have variant wrapping enum of the flattened type.
+///  Aliases of flattened fields and `lookup` mode of the struct apply to their names, but `<Struct>FieldName`
+///  has no variants for them and `set_field_str` returns `NotParsable` error for them.
+///  Clashes of field names and aliases, also ambiguous with `lookup` mode, are compile errors.
+///  It can be combined only with `skip_mut` and `skip_iter`
+///  and type of the field cannot depend on generic parameters of the struct
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess, Default)]
+/// struct Audit {
+///     created_at: u64,
+///     updated_by: String,
+/// }
+///
+/// #[derive(FieldnameAccess, Default)]
+/// struct Record {
+///     id: u64,
+///     #[fieldname(flatten)]
+///     audit: Audit,
+/// }
+///
+/// let mut record = Record::default();
+/// assert_eq!(Record::FIELDS, ["id", "created_at", "updated_by"]);
+/// match record.field("created_at") {
+///     Some(RecordField::Audit(AuditField::U64(created_at))) => assert_eq!(*created_at, 0),
+///     _ => unreachable!(),
+/// }
+/// record
+///     .set_field("created_at", RecordFieldValue::Audit(AuditFieldValue::U64(7)))
+///     .unwrap();
+/// assert_eq!(record.audit.created_at, 7);
+///```
+///
+///* `#fieldname(skip)` - Exclude field from all generated enums, methods and `FIELDS`.
+///  `#fieldname(skip_mut)` makes field read-only: it is not accessible by `field_mut`, `field_at_mut`
+///  and `field_by_name_mut`, which returns `Option` then, and `set_field` returns `ReadOnly` error.
+///  All fields can be read-only, `field_mut` always returns `None` then.
+///  `#fieldname(skip_iter)` keeps field accessible by name but hides it from `field_iter`
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// struct Cache;
+///
+/// #[derive(FieldnameAccess)]
+/// struct Session {
+///     #[fieldname(skip)]
+///     cache: Cache,
+///     #[fieldname(skip_mut)]
+///     id: u64,
+///     #[fieldname(skip_iter)]
+///     password: String,
+/// }
+///
+/// let mut session = Session { cache: Cache, id: 1, password: String::from("ranni") };
+/// assert_eq!(Session::FIELDS, ["id", "password"]);
+/// assert!(session.field("cache").is_none());
+/// assert!(session.field_mut("id").is_none());
+/// assert!(session.field_mut("password").is_some());
+/// assert_eq!(session.field_iter().map(|(name, _)| name).collect::<Vec<_>>(), ["id"]);
+///```
+///
+///### Enums
+///
+/// Enums with any kind of variants are supported too. `field` and `field_mut` return field of the
+/// active variant or `None` if it doesn't have such field, so fields shared by several variants
+/// are accessible without matching on the variant. `FIELDS` contains fields of all variants,
+/// `VARIANT_FIELDS` contains fields of every variant and `fields` method returns fields of the active one
+///
+///```rust
+/// use fieldname_access::FieldnameAccess;
+///
+/// #[derive(FieldnameAccess)]
+/// enum Shape {
+///     Circle { id: u32, radius: f64 },
+///     Rect { id: u32, w: f64, h: f64 },
+/// }
+///
+/// let shape = Shape::Circle { id: 7, radius: 1.0 };
+/// assert_eq!(Shape::FIELDS, ["id", "radius", "w", "h"]);
+/// assert_eq!(shape.fields(), ["id", "radius"]);
+/// match shape.field("id").unwrap() {
+///     ShapeField::U32(id) => assert_eq!(*id, 7),
+///     ShapeField::F64(_) => unreachable!(),
+/// }
+/// assert!(shape.field("w").is_none());
+///```
+pub use fieldname_access_derive::FieldnameAccess;
 
-#[proc_macro_derive(FieldnameAccess, attributes(fieldname_enum, fieldname))]
-pub fn fieldname_accessor(inp: TokenStream) -> TokenStream {
-    let inp = parse_macro_input!(inp as DeriveInput);
-    let structure = match inp.data {
-        Data::Struct(ref s) => s,
-        Data::Union(_) => {
-            panic!("FieldnameAccess cannot be used with unions")
-        }
-        Data::Enum(_) => {
-            panic!("FieldnameAccess cannot be used with enums")
-        }
-    };
-    let DeriveInput {
-        ident: struct_ident,
-        vis: visibility,
-        generics,
-        ..
-    } = inp;
-
-    let field_lifetime: syn::GenericParam = parse_quote!('field);
-    let (impl_generics, ty_generics, where_clauses) = generics.split_for_impl();
-
-    let mut enum_generics = generics.clone();
-    enum_generics.params.push(field_lifetime.clone());
-
-    let fields = match &structure.fields {
-        Fields::Named(FieldsNamed { named: x, .. }) => x.to_owned(),
-        Fields::Unnamed(_) | Fields::Unit => {
-            panic!("Nameless fields are not supported")
-        }
-    };
-
-    let field_map = fields
-        .into_iter()
-        .map(|field| {
-            let field_type = field.ty;
-            let field_name = field.ident.expect("Nameless fields are not supported");
-            let variant_ident = if let Some(name) = retrieve_fieldname(&field.attrs) {
-                name
-            } else {
-                let type_str = generate_variant_name(&field_type);
-                Ident::new(&type_str, Span::call_site())
-            };
-            (field_name, field_type, variant_ident)
-        })
-        .collect::<Vec<_>>();
-    let field_list = field_map.iter().map(|(name, _, _)| name.to_string());
-    let field_count = field_map.len();
-
-    let (derive, derive_mut) = if let Some(derives) = retrieve_derives(&inp.attrs, "derive_all") {
-        (Some(derives.clone()), Some(derives))
-    } else {
-        let derive = retrieve_derives(&inp.attrs, "derive");
-        let derive_mut = retrieve_derives(&inp.attrs, "derive_mut");
-        (derive, derive_mut)
-    };
-
-    let value_enum_ident = retrieve_enum_name(&inp.attrs).unwrap_or(Ident::new(
-        &format!("{}Field", struct_ident),
-        Span::call_site(),
-    ));
-    let value_enum_ident_mut = Ident::new(&format!("{}Mut", value_enum_ident), Span::call_site());
-
-    let value_variants = generate_enum_variants(&field_map, false);
-    let value_variants_mut = generate_enum_variants(&field_map, true);
-
-    let match_arms = generate_match_arms(&field_map, &value_enum_ident, false);
-    let match_arms_mut = generate_match_arms(&field_map, &value_enum_ident_mut, true);
-
-    let iter_impl = generate_iter_impl(
-        &visibility,
-        &value_enum_ident,
-        &struct_ident,
-        &ty_generics,
-        &where_clauses,
-        &enum_generics,
-        &field_lifetime,
-    );
-
-    let tokens = quote! {
-        /// Enum with reference to possible field
-        #derive
-        #visibility enum #value_enum_ident #enum_generics {
-            #(#value_variants,)*
-        }
-
-        /// Enum with mutable reference to possible field
-        #derive_mut
-        #visibility enum #value_enum_ident_mut #enum_generics {
-            #(#value_variants_mut,)*
-        }
-
-        #iter_impl
-
-        impl #impl_generics #struct_ident #ty_generics #where_clauses {
-            /// List with all struct fields
-            const FIELDS: [&'static str; #field_count] = [#(#field_list),*];
-
-            /// Method for getting reference to struct field by its name
-            #visibility fn field<#field_lifetime>(&#field_lifetime self, fieldname: &str) -> Option<#value_enum_ident #enum_generics> {
-                match fieldname {
-                    #(#match_arms,)*
-                    _ => None
-                }
-            }
-            /// Method for getting mutable reference to struct field by its name
-            #visibility fn field_mut<#field_lifetime>(&#field_lifetime mut self, fieldname: &str) -> Option<#value_enum_ident_mut #enum_generics> {
-                match fieldname {
-                    #(#match_arms_mut,)*
-                    _ => None
-                }
-            }
-        }
-    };
-    tokens.into()
-}
-
-fn generate_variant_name(ty: &syn::Type) -> String {
-    let type_str = ty.to_token_stream().to_string();
-    shorten_type(type_str)
-}
-
-fn generate_iter_impl(
-    vis: &Visibility,
-    value_enum_ident: &Ident,
-    struct_ident: &Ident,
-    struct_generics: &TypeGenerics,
-    where_clauses: &Option<&WhereClause>,
-    enum_generics: &Generics,
-    enum_lt: &syn::GenericParam,
-) -> proc_macro2::TokenStream {
-    let iter_ident = Ident::new(&format!("{}FieldIter", value_enum_ident), Span::call_site());
-
-    let struct_generic_turbofish = struct_generics.as_turbofish();
-    let struct_ident_turbofish = quote! { #struct_ident #struct_generic_turbofish };
+mod error;
 
-    quote! {
-        #vis struct #iter_ident #enum_generics #where_clauses {
-            idx: usize,
-            inner: &#enum_lt #struct_ident #struct_generics
-        }
+pub use error::{
+    FieldNameError, FieldPathError, FieldPathErrorKind, ParseFieldError, SetFieldError,
+    UnknownField,
+};
 
-        impl #struct_generics #struct_ident #struct_generics #where_clauses {
-            pub fn field_iter<#enum_lt>(&#enum_lt self) -> #iter_ident #enum_generics {
-                #iter_ident {
-                    idx: 0,
-                    inner: self
-                }
-            }
-        }
+/// Access to fields by their names implemented by `FieldnameAccess` derive, so code can be generic
+/// over derived types. Methods and `FIELDS` are the same as inherent ones of the type
+pub trait FieldnameAccess {
+    /// Enum with reference to possible field, `<Type>Field`
+    type Field<'field>
+    where
+        Self: 'field;
+    /// Enum with mutable reference to possible field, `<Type>FieldMut`
+    type FieldMut<'field>
+    where
+        Self: 'field;
+    /// Iterator returned by `field_iter`
+    type FieldIter<'field>: Iterator<Item = (&'static str, Self::Field<'field>)>
+    where
+        Self: 'field;
 
-        impl #enum_generics Iterator for #iter_ident #enum_generics #where_clauses {
-            type Item = (&'static str, #value_enum_ident #enum_generics);
+    /// List with all fields
+    const FIELDS: &'static [&'static str];
 
-            fn next(&mut self) -> Option<Self::Item> {
-                (self.idx != #struct_ident_turbofish::FIELDS.len()).then(|| {
-                    let field_name = #struct_ident_turbofish::FIELDS[self.idx];
-                    self.idx += 1;
-                    (field_name, self.inner.field(field_name).unwrap())
-                })
-            }
-        }
-    }
+    /// Returns reference to field by its name
+    fn field<'field>(&'field self, fieldname: &str) -> Option<Self::Field<'field>>;
+    /// Returns mutable reference to field by its name
+    fn field_mut<'field>(&'field mut self, fieldname: &str) -> Option<Self::FieldMut<'field>>;
+    /// Returns iterator over field names and references to fields
+    fn field_iter<'field>(&'field self) -> Self::FieldIter<'field>;
 }
 
-fn shorten_type(type_str: String) -> String {
-    let mut short_type = type_str
-        .chars()
-        .skip_while(|c| !c.is_uppercase())
-        .peekable();
-    if short_type.peek().is_some() {
-        let mut complex_type_str = String::new();
-        while let Some(c) = short_type.next() {
-            if c.is_ascii_alphanumeric() {
-                complex_type_str.push(c);
-            }
-            if c == '<' {
-                complex_type_str += &shorten_type(short_type.collect());
-                break;
-            }
-        }
-        complex_type_str
-    } else {
-        let cleaned_str = type_str
-            .chars()
-            .filter(|c| c.is_ascii_alphanumeric())
-            .collect::<String>();
-        cleaned_str[0..1].to_uppercase() + &cleaned_str[1..]
-    }
-}
+/// Field of `T` represented by zero-sized marker type generated by `FieldnameAccess` derive,
+/// like `user_fields::Age` for `age` field of `User`
+pub trait Field<T> {
+    /// Type of the field
+    type Value;
+    /// Name of the field used for access by string
+    const NAME: &'static str;
 
-fn generate_enum_variants(
-    field_map: &[(Ident, syn::Type, Ident)],
-    is_mut: bool,
-) -> Vec<proc_macro2::TokenStream> {
-    field_map
-        .iter()
-        .unique_by(|(_, _, variant_ident)| variant_ident)
-        .map(|(_, field_type, variant_ident)| {
-            if is_mut {
-                quote! {
-                    #variant_ident(&'field mut #field_type)
-                }
-            } else {
-                quote! {
-                    #variant_ident(&'field #field_type)
-                }
-            }
-        })
-        .collect()
+    /// Returns reference to the field of `owner`
+    fn get(owner: &T) -> &Self::Value;
 }
 
-fn generate_match_arms(
-    field_map: &[(Ident, Type, Ident)],
-    value_enum_ident: &Ident,
-    is_mut: bool,
-) -> Vec<proc_macro2::TokenStream> {
-    field_map
-        .iter()
-        .map(|(field_name, _, variant_ident)| {
-            let field_name_str = field_name.to_string();
-            if is_mut {
-                quote! {
-                    #field_name_str => Some(#value_enum_ident::#variant_ident(&mut self.#field_name))
-                }
-            } else {
-                quote! {
-                    #field_name_str => Some(#value_enum_ident::#variant_ident(&self.#field_name))
-                }
-            }
-        })
-        .collect()
+/// Field of `T` which is accessible mutably, it is not implemented for `#[fieldname(skip_mut)]` fields
+pub trait FieldMut<T>: Field<T> {
+    /// Returns mutable reference to the field of `owner`
+    fn get_mut(owner: &mut T) -> &mut Self::Value;
 }
 
-fn retrieve_enum_name(attrs: &[Attribute]) -> Option<Ident> {
-    if let Some(TokenTree::Literal(lit)) = get_fieldname_enum_val(attrs, "name") {
-        let lit = lit.to_string();
-        Some(Ident::new(&lit[1..lit.len() - 1], Span::call_site()))
-    } else {
-        None
+/// Access to fields of derived types by their marker types, like `user.get::<user_fields::Age>()`.
+/// It's a trait rather than inherent methods so types can have their own `get` and `get_mut`
+pub trait FieldnameAccessExt: FieldnameAccess + Sized {
+    /// Returns reference to the field marked by `F`
+    fn get<F: Field<Self>>(&self) -> &F::Value {
+        F::get(self)
     }
-}
 
-fn retrieve_derives(attrs: &[Attribute], derive_group: &str) -> Option<proc_macro2::TokenStream> {
-    if let Some(TokenTree::Group(group)) = get_fieldname_enum_val(attrs, derive_group) {
-        let token_stream = group.stream();
-        Some(quote!(#[derive(#token_stream)]))
-    } else {
-        None
+    /// Returns mutable reference to the field marked by `F`
+    fn get_mut<F: FieldMut<Self>>(&mut self) -> &mut F::Value {
+        F::get_mut(self)
     }
 }
 
-fn retrieve_fieldname(attrs: &[Attribute]) -> Option<Ident> {
-    attrs.iter().find_map(|attr| match &attr.meta {
-        Meta::NameValue(meta_name_value) => {
-            let fieldname_enum_attr = meta_name_value.path.segments.first()?;
-            if fieldname_enum_attr.ident != "fieldname" {
-                return None;
-            }
-            if let Expr::Lit(ExprLit {
-                lit: Lit::Str(ref str),
-                ..
-            }) = meta_name_value.value
-            {
-                Some(Ident::new(&str.value(), Span::call_site()))
-            } else {
-                None
-            }
-        }
-
-        _ => None,
-    })
-}
-
-fn get_fieldname_enum_val(attrs: &[Attribute], attr_name: &str) -> Option<TokenTree> {
-    attrs.iter().find_map(|attr| match &attr.meta {
-        Meta::List(meta_list) => {
-            let fieldname_enum_attr = meta_list.path.segments.first()?;
-            if fieldname_enum_attr.ident != "fieldname_enum" {
-                return None;
-            }
-            meta_list
-                .tokens
-                .clone()
-                .into_iter()
-                .skip_while(|token| token.to_string() != attr_name)
-                .nth(2)
-        }
-        _ => None,
-    })
-}
+impl<T: FieldnameAccess> FieldnameAccessExt for T {}
@@ -0,0 +1,204 @@
+use std::fmt::{Debug, Display};
+
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct Borrowed<'a, 'b: 'a> {
+    name: &'a str,
+    tags: &'b [String],
+}
+
+#[derive(FieldnameAccess)]
+struct Names<'a, 'b> {
+    first: &'a str,
+    last: &'b str,
+    nickname: Option<&'a str>,
+    title: Option<&'b str>,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive_all = [Debug])]
+struct Bounded<T: Clone + Debug, U: ?Sized + Display> {
+    value: T,
+    label: Box<U>,
+}
+
+#[derive(FieldnameAccess)]
+struct Defaulted<T = u8, const N: usize = 2> {
+    value: T,
+    #[fieldname = "Array"]
+    items: [u16; N],
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(parse)]
+struct Filtered<T>
+where
+    T: Clone + std::str::FromStr,
+    T::Err: Display,
+{
+    value: T,
+    count: u32,
+}
+
+#[derive(FieldnameAccess)]
+struct Cached<'a, T, U> {
+    #[fieldname(skip_mut)]
+    key: &'a T,
+    #[fieldname(skip)]
+    _cache: Option<U>,
+    hits: u32,
+}
+
+#[derive(FieldnameAccess)]
+struct Everything<'a, T: Clone, const N: usize>
+where
+    T: Debug + 'a,
+{
+    slice: &'a [T],
+    #[fieldname = "Array"]
+    array: [T; N],
+}
+
+#[derive(FieldnameAccess)]
+enum Either<L, R = String>
+where
+    L: Display,
+{
+    Left { value: L },
+    Right(R),
+}
+
+#[test]
+fn lifetimes() {
+    let tags = [String::from("x")];
+    let mut borrowed = Borrowed {
+        name: "ranni",
+        tags: &tags,
+    };
+    assert!(matches!(
+        borrowed.field("name"),
+        Some(BorrowedField::RefStr(&"ranni"))
+    ));
+    if let Some(BorrowedFieldMut::RefStr(name)) = borrowed.field_mut("name") {
+        *name = "blaidd";
+    }
+    assert_eq!(borrowed.name, "blaidd");
+    assert_eq!(borrowed.field_iter().len(), 2);
+
+    let names = Names {
+        first: "ranni",
+        last: "carian",
+        nickname: None,
+        title: Some("witch"),
+    };
+    assert!(matches!(
+        names.field("first"),
+        Some(NamesField::RefAStr(&"ranni"))
+    ));
+    assert!(matches!(
+        names.field("last"),
+        Some(NamesField::RefBStr(&"carian"))
+    ));
+    assert!(matches!(
+        names.field("nickname"),
+        Some(NamesField::OptionRefAStr(None))
+    ));
+    assert!(matches!(
+        names.field("title"),
+        Some(NamesField::OptionRefBStr(Some("witch")))
+    ));
+}
+
+#[test]
+fn bounds() {
+    let bounded: Bounded<u8, str> = Bounded {
+        value: 1,
+        label: Box::from("one"),
+    };
+    assert_eq!(format!("{:?}", bounded.field("value")), "Some(T(1))");
+    assert!(matches!(bounded.field("label"), Some(BoundedField::BoxU(label)) if &**label == "one"));
+}
+
+#[test]
+fn defaults() {
+    let mut defaulted: Defaulted = Defaulted {
+        value: 0,
+        items: [0; 2],
+    };
+    assert!(matches!(
+        defaulted.field_path("items[1]"),
+        Ok(DefaultedFieldPath::Items(0))
+    ));
+    if let Ok(DefaultedFieldPathMut::Items(item)) = defaulted.field_path_mut("items[0]") {
+        *item = 4;
+    }
+    assert_eq!(defaulted.items, [4, 0]);
+    let wide = Defaulted {
+        value: 0i64,
+        items: [0; 3],
+    };
+    assert!(matches!(wide.field("items"), Some(DefaultedField::Array(items)) if items.len() == 3));
+}
+
+#[test]
+fn where_clauses() {
+    let mut filtered = Filtered {
+        value: 1u64,
+        count: 0,
+    };
+    filtered.set_field_str("value", "42").unwrap();
+    assert_eq!(filtered.value, 42);
+    assert!(matches!(
+        filtered.set_field("count", FilteredFieldValue::U32(3)),
+        Ok(FilteredFieldValue::U32(0))
+    ));
+
+    let either = Either::<u8>::Right(String::from("right"));
+    assert!(matches!(either.field("0"), Some(EitherField::R(right)) if right == "right"));
+    assert!(Either::<u8>::Left { value: 1 }.field("0").is_none());
+}
+
+#[test]
+fn unused_in_some_enums() {
+    let key = 5u8;
+    let mut cached = Cached::<u8, String> {
+        key: &key,
+        _cache: None,
+        hits: 1,
+    };
+    assert_eq!(Cached::<u8, String>::FIELDS, ["key", "hits"]);
+    assert!(matches!(cached.field("key"), Some(CachedField::RefT(5))));
+    assert!(cached.field_mut("key").is_none());
+    for (_, field) in cached.field_iter_mut() {
+        match field {
+            CachedFieldMut::U32(hits) => *hits += 1,
+            _ => unreachable!(),
+        }
+    }
+    assert_eq!(cached.hits, 2);
+    assert_eq!(
+        cached
+            .set_field("hits", CachedFieldValue::U32(0))
+            .unwrap()
+            .variant_name(),
+        "U32"
+    );
+}
+
+#[test]
+fn everything() {
+    let source = [1u8, 2];
+    let everything = Everything {
+        slice: &source,
+        array: [3u8, 4, 5],
+    };
+    assert_eq!(Everything::<u8, 3>::FIELD_COUNT, 2);
+    assert!(matches!(
+        everything.field_path("array[2]"),
+        Ok(EverythingFieldPath::Array(5))
+    ));
+    assert!(
+        matches!(everything.field_at(0), Some(EverythingField::RefSliceT(slice)) if slice.len() == 2)
+    );
+}
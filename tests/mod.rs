@@ -1,6 +1,10 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 
-use fieldname_access::FieldnameAccess;
+use fieldname_access::{
+    Field, FieldMut, FieldNameError, FieldPathError, FieldPathErrorKind, FieldnameAccess,
+    FieldnameAccessExt, ParseFieldError, SetFieldError,
+};
 
 #[derive(FieldnameAccess)]
 struct TestStruct {
@@ -218,6 +222,7 @@ fn test_complex_type_path() {
 }
 
 #[derive(FieldnameAccess, Clone, Copy)]
+#[fieldname_enum(into_fields)]
 struct GenericStruct<'a, T, F>
 where
     T: Display,
@@ -234,7 +239,7 @@ fn generic_struct_fieldname_access() {
         name: &String::from("123"),
     };
     match structure.field("name").unwrap() {
-        GenericStructField::T(name) => assert_eq!(*name, "123"),
+        GenericStructField::RefT(name) => assert_eq!(*name, "123"),
         GenericStructField::F(_age) => panic!(),
     }
 }
@@ -257,6 +262,13 @@ struct NamedFieldnameTwo {
     #[fieldname = "AmazingAge"]
     age: i64,
 }
+#[derive(FieldnameAccess)]
+#[fieldname_enum(name = "derive", derive = [Debug])]
+#[allow(unused)]
+struct NamedLikeOption {
+    name: String,
+}
+
 #[test]
 fn attributes() {
     let mut structure = NamedFieldname {
@@ -283,6 +295,15 @@ fn attributes() {
     }
 }
 
+#[test]
+fn enum_name_equal_to_option_name() {
+    let structure = NamedLikeOption {
+        name: String::from("Ranni"),
+    };
+    let derive::String(name) = structure.field("name").unwrap();
+    assert_eq!(format!("{:?}", derive::String(name)), "String(\"Ranni\")");
+}
+
 #[test]
 fn const_field_list() {
     assert_eq!(
@@ -344,7 +365,1785 @@ where
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             GenericStructField::F(smth) => write!(f, "{}", smth),
-            GenericStructField::T(smth) => write!(f, "{}", smth),
+            GenericStructField::RefT(smth) => write!(f, "{}", smth),
+        }
+    }
+}
+
+#[derive(FieldnameAccess)]
+struct Rgb(u8, u8, u8);
+
+#[derive(FieldnameAccess)]
+struct NamedRgb(
+    #[fieldname(name = "r")] u8,
+    #[fieldname(name = "g")] u8,
+    #[fieldname(name = "b")] u8,
+);
+
+#[derive(FieldnameAccess)]
+struct Meters(#[fieldname = "Length"] f64);
+
+#[test]
+fn tuple_struct_access() {
+    let mut color = Rgb(1, 2, 3);
+    assert_eq!(Rgb::FIELDS, ["0", "1", "2"]);
+
+    match color.field("1") {
+        Some(RgbField::U8(val)) => assert_eq!(*val, 2),
+        None => panic!("Failed"),
+    }
+    match color.field_at(2) {
+        Some(RgbField::U8(val)) => assert_eq!(*val, 3),
+        None => panic!("Failed"),
+    }
+    if let Some(RgbFieldMut::U8(val)) = color.field_at_mut(0) {
+        *val = 10;
+    }
+    assert_eq!(color.0, 10);
+    assert!(color.field("3").is_none());
+    assert!(color.field_at(3).is_none());
+
+    match Meters(1.5).field("0") {
+        Some(MetersField::Length(length)) => assert_eq!(*length, 1.5),
+        None => panic!("Failed"),
+    }
+}
+
+#[test]
+fn tuple_struct_field_names() {
+    let mut color = NamedRgb(1, 2, 3);
+    assert_eq!(NamedRgb::FIELDS, ["r", "g", "b"]);
+
+    if let Some(NamedRgbFieldMut::U8(val)) = color.field_mut("g") {
+        *val = 20;
+    }
+    assert_eq!(color.1, 20);
+    assert!(color.field("1").is_none());
+
+    let res = color
+        .field_iter()
+        .map(|(name, NamedRgbField::U8(val))| format!("{}={}", name, val))
+        .collect::<Vec<_>>();
+    assert_eq!(res.join("\n"), "r=1\ng=20\nb=3");
+}
+
+#[test]
+fn named_field_at() {
+    let structure = NamedFieldname {
+        age: 1,
+        name: String::from("boba"),
+        dog_age: 2,
+        cat_age: 3,
+    };
+    match structure.field_at(3) {
+        Some(Amazingly::I64(val)) => assert_eq!(*val, 3),
+        _ => panic!("Failed"),
+    }
+    assert!(structure.field_at(4).is_none());
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive_all = [Debug], into_fields)]
+enum Shape {
+    Circle { id: u32, radius: f64 },
+    Rect { id: u32, w: f64, h: f64 },
+    Labeled(u32, String),
+    Empty,
+}
+
+#[test]
+fn enum_field_lists() {
+    assert_eq!(Shape::FIELDS, ["id", "radius", "w", "h", "0", "1"]);
+    assert_eq!(
+        Shape::VARIANT_FIELDS,
+        [
+            ("Circle", &["id", "radius"][..]),
+            ("Rect", &["id", "w", "h"][..]),
+            ("Labeled", &["0", "1"][..]),
+            ("Empty", &[][..]),
+        ]
+    );
+    assert_eq!(
+        Shape::Circle { id: 1, radius: 2.0 }.fields(),
+        ["id", "radius"]
+    );
+    assert!(Shape::Empty.fields().is_empty());
+}
+
+#[test]
+fn enum_access() {
+    let circle = Shape::Circle { id: 1, radius: 2.0 };
+    let rect = Shape::Rect {
+        id: 2,
+        w: 3.0,
+        h: 4.0,
+    };
+
+    for (shape, expected_id) in [(&circle, 1), (&rect, 2)] {
+        match shape.field("id") {
+            Some(ShapeField::U32(id)) => assert_eq!(*id, expected_id),
+            _ => panic!("Failed"),
+        }
+    }
+    match circle.field("radius") {
+        Some(ShapeField::F64(radius)) => assert_eq!(*radius, 2.0),
+        _ => panic!("Failed"),
+    }
+    assert!(circle.field("w").is_none());
+    assert!(rect.field("radius").is_none());
+    assert!(Shape::Empty.field("id").is_none());
+
+    match Shape::Labeled(3, String::from("Ranni")).field_at(1) {
+        Some(ShapeField::String(label)) => assert_eq!(label, "Ranni"),
+        _ => panic!("Failed"),
+    }
+    match rect.field_at(2) {
+        Some(ShapeField::F64(h)) => assert_eq!(*h, 4.0),
+        _ => panic!("Failed"),
+    }
+    assert!(circle.field_at(2).is_none());
+}
+
+#[test]
+fn enum_access_mut() {
+    let mut shape = Shape::Rect {
+        id: 2,
+        w: 3.0,
+        h: 4.0,
+    };
+    if let Some(ShapeFieldMut::U32(id)) = shape.field_mut("id") {
+        *id = 5;
+    }
+    if let Some(ShapeFieldMut::F64(w)) = shape.field_at_mut(1) {
+        *w = 6.0;
+    }
+    assert!(shape.field_mut("radius").is_none());
+    match shape {
+        Shape::Rect { id, w, .. } => {
+            assert_eq!(id, 5);
+            assert_eq!(w, 6.0);
+        }
+        _ => panic!("Failed"),
+    }
+}
+
+#[test]
+fn enum_iter() {
+    let shape = Shape::Circle { id: 1, radius: 2.0 };
+    let res = shape
+        .field_iter()
+        .map(|(name, val)| format!("{}={:?}", name, val))
+        .collect::<Vec<_>>();
+    assert_eq!(res.join("\n"), "id=U32(1)\nradius=F64(2.0)");
+    assert_eq!(Shape::Empty.field_iter().count(), 0);
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive_value = [Debug, PartialEq], into_fields)]
+struct Message {
+    topic: String,
+    partition: u32,
+    payload: Vec<u8>,
+}
+
+#[test]
+fn owned_field() {
+    let message = Message {
+        topic: String::from("users"),
+        partition: 3,
+        payload: vec![1, 2, 3],
+    };
+    assert_eq!(
+        message.into_field("payload"),
+        Some(MessageFieldValue::VecU8(vec![1, 2, 3]))
+    );
+
+    let message = Message {
+        topic: String::from("users"),
+        partition: 3,
+        payload: vec![1, 2, 3],
+    };
+    assert_eq!(message.into_field("key"), None);
+}
+
+#[test]
+fn owned_fields() {
+    let message = Message {
+        topic: String::from("users"),
+        partition: 3,
+        payload: vec![1, 2, 3],
+    };
+    assert_eq!(
+        message.into_fields().collect::<Vec<_>>(),
+        [
+            ("topic", MessageFieldValue::String(String::from("users"))),
+            ("partition", MessageFieldValue::U32(3)),
+            ("payload", MessageFieldValue::VecU8(vec![1, 2, 3])),
+        ]
+    );
+
+    let fields = Shape::Rect {
+        id: 2,
+        w: 3.0,
+        h: 4.0,
+    }
+    .into_fields()
+    .map(|(name, val)| format!("{}={:?}", name, val))
+    .collect::<Vec<_>>();
+    assert_eq!(fields.join("\n"), "id=U32(2)\nw=F64(3.0)\nh=F64(4.0)");
+    assert_eq!(Shape::Empty.into_fields().count(), 0);
+
+    let name = String::from("boba");
+    let fields = GenericStruct {
+        age: 123,
+        name: &name,
+    }
+    .into_fields()
+    .map(|(name, val)| match val {
+        GenericStructFieldValue::RefT(val) => format!("{}={}", name, val),
+        GenericStructFieldValue::F(val) => format!("{}={}", name, val),
+    })
+    .collect::<Vec<_>>();
+    assert_eq!(fields.join("\n"), "name=boba\nage=123");
+}
+
+#[derive(FieldnameAccess)]
+struct Connection {
+    host: String,
+    open: bool,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.open = false;
+    }
+}
+
+#[test]
+fn drop_types_without_into_fields() {
+    let mut connection = Connection {
+        host: String::from("localhost"),
+        open: true,
+    };
+    assert!(matches!(
+        connection.set_field("open", ConnectionFieldValue::Bool(false)),
+        Ok(ConnectionFieldValue::Bool(true))
+    ));
+    assert!(
+        matches!(connection.field("host"), Some(ConnectionField::String(host)) if host == "localhost")
+    );
+}
+
+#[test]
+fn set_field() {
+    let mut message = Message {
+        topic: String::from("users"),
+        partition: 3,
+        payload: vec![1, 2, 3],
+    };
+
+    assert_eq!(
+        message.set_field("partition", MessageFieldValue::U32(7)),
+        Ok(MessageFieldValue::U32(3))
+    );
+    assert_eq!(message.partition, 7);
+
+    let err = message
+        .set_field("topic", MessageFieldValue::U32(1))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        SetFieldError::MismatchedValue {
+            field: "topic",
+            expected: "String",
+            found: "U32",
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "field `topic` expects `String` value, found `U32`"
+    );
+    assert_eq!(message.topic, "users");
+
+    let err = message
+        .set_field("key", MessageFieldValue::U32(1))
+        .unwrap_err();
+    assert_eq!(err, SetFieldError::UnknownField(String::from("key")));
+    assert_eq!(err.to_string(), "unknown field `key`");
+}
+
+#[test]
+fn set_enum_field() {
+    let mut shape = Shape::Circle { id: 1, radius: 2.0 };
+    assert!(matches!(
+        shape.set_field("radius", ShapeFieldValue::F64(5.0)),
+        Ok(ShapeFieldValue::F64(radius)) if radius == 2.0
+    ));
+    assert_eq!(
+        shape.set_field("w", ShapeFieldValue::F64(1.0)).unwrap_err(),
+        SetFieldError::UnknownField(String::from("w"))
+    );
+    assert_eq!(ShapeFieldValue::U32(1).variant_name(), "U32");
+}
+
+fn parse_tags(raw: &str) -> Result<Vec<String>, std::convert::Infallible> {
+    Ok(raw.split(',').map(String::from).collect())
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(parse)]
+#[allow(unused)]
+struct AdminUser {
+    name: String,
+    age: u64,
+    does_love_ranni: bool,
+    #[fieldname(parse_with = "parse_tags")]
+    tags: Vec<String>,
+    #[fieldname(no_parse)]
+    important: Option<ImportantInfo>,
+}
+
+#[test]
+fn set_field_from_str() {
+    let mut user = AdminUser {
+        name: String::from("Radahn"),
+        age: 2022,
+        does_love_ranni: true,
+        tags: Vec::new(),
+        important: None,
+    };
+
+    user.set_field_str("name", "Ranni").unwrap();
+    user.set_field_str("age", "18").unwrap();
+    user.set_field_str("does_love_ranni", "false").unwrap();
+    user.set_field_str("tags", "witch,doll").unwrap();
+    assert_eq!(user.name, "Ranni");
+    assert_eq!(user.age, 18);
+    assert!(!user.does_love_ranni);
+    assert_eq!(user.tags, ["witch", "doll"]);
+
+    let err = user.set_field_str("age", "old").unwrap_err();
+    assert_eq!(
+        err,
+        ParseFieldError::InvalidValue {
+            field: "age",
+            message: String::from("invalid digit found in string"),
         }
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid value for field `age`: invalid digit found in string"
+    );
+    assert_eq!(user.age, 18);
+
+    assert_eq!(
+        user.set_field_str("important", "yes"),
+        Err(ParseFieldError::NotParsable("important"))
+    );
+    assert_eq!(
+        user.set_field_str("height", "2"),
+        Err(ParseFieldError::UnknownField(String::from("height")))
+    );
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(parse)]
+struct GenericParse<T> {
+    value: T,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(parse)]
+enum Setting {
+    Port { port: u16 },
+    Host { host: String, port: u16 },
+}
+
+#[test]
+fn set_field_from_str_generic_and_enum() {
+    let mut structure = GenericParse { value: 1.5_f64 };
+    structure.set_field_str("value", "2.5").unwrap();
+    assert_eq!(structure.value, 2.5);
+
+    let mut setting = Setting::Host {
+        host: String::from("localhost"),
+        port: 80,
+    };
+    setting.set_field_str("port", "8080").unwrap();
+    match setting {
+        Setting::Host { port, .. } => assert_eq!(port, 8080),
+        Setting::Port { .. } => panic!("Failed"),
+    }
+    assert_eq!(
+        Setting::Port { port: 1 }.set_field_str("host", "example.com"),
+        Err(ParseFieldError::UnknownField(String::from("host")))
+    );
+}
+
+#[test]
+fn field_name_enum() {
+    use std::convert::TryFrom;
+
+    assert_eq!(
+        AdminUserFieldName::ALL,
+        [
+            AdminUserFieldName::Name,
+            AdminUserFieldName::Age,
+            AdminUserFieldName::DoesLoveRanni,
+            AdminUserFieldName::Tags,
+            AdminUserFieldName::Important,
+        ]
+    );
+    assert_eq!(
+        AdminUserFieldName::DoesLoveRanni.as_str(),
+        "does_love_ranni"
+    );
+    assert_eq!(
+        AdminUserFieldName::DoesLoveRanni.to_string(),
+        "does_love_ranni"
+    );
+    assert_eq!(AdminUserFieldName::Tags.index(), 3);
+    assert_eq!(
+        "age".parse::<AdminUserFieldName>(),
+        Ok(AdminUserFieldName::Age)
+    );
+    assert_eq!(
+        "agee".parse::<AdminUserFieldName>(),
+        Err(FieldNameError::UnknownName(String::from("agee")))
+    );
+    assert_eq!(AdminUserFieldName::try_from(1), Ok(AdminUserFieldName::Age));
+    assert_eq!(
+        AdminUserFieldName::try_from(5).unwrap_err().to_string(),
+        "there is no field at position 5"
+    );
+
+    for (name, field) in AdminUserFieldName::ALL.iter().zip(AdminUser::FIELDS) {
+        assert_eq!(name.as_str(), field);
+    }
+    assert_eq!(
+        NamedRgbFieldName::ALL,
+        [
+            NamedRgbFieldName::R,
+            NamedRgbFieldName::G,
+            NamedRgbFieldName::B
+        ]
+    );
+    assert_eq!(RgbFieldName::Field2.as_str(), "2");
+}
+
+#[test]
+fn field_by_name() {
+    let mut user = AdminUser {
+        name: String::from("Radahn"),
+        age: 2022,
+        does_love_ranni: true,
+        tags: Vec::new(),
+        important: None,
+    };
+
+    let name = "age".parse::<AdminUserFieldName>().unwrap();
+    match user.field_by_name(name) {
+        AdminUserField::U64(age) => assert_eq!(*age, 2022),
+        _ => panic!("Failed"),
+    }
+    if let AdminUserFieldMut::U64(age) = user.field_by_name_mut(name) {
+        *age = 18;
+    }
+    assert_eq!(user.age, 18);
+
+    let mut shape = Shape::Circle { id: 1, radius: 2.0 };
+    assert!(matches!(
+        shape.field_by_name(ShapeFieldName::Radius),
+        Some(ShapeField::F64(radius)) if *radius == 2.0
+    ));
+    assert!(shape.field_by_name_mut(ShapeFieldName::W).is_none());
+    assert_eq!(ShapeFieldName::Field1.as_str(), "1");
+}
+
+#[allow(dead_code)]
+mod catalog {
+    use fieldname_access::FieldnameAccess;
+
+    #[derive(FieldnameAccess)]
+    pub struct Product {
+        sku: String,
+        price: u32,
+    }
+
+    #[derive(FieldnameAccess)]
+    #[fieldname_enum(fields_vis = "pub(crate)")]
+    pub struct Draft {
+        title: String,
+    }
+
+    #[derive(FieldnameAccess)]
+    #[fieldname_enum(fields_vis = "pub(crate)")]
+    pub enum Entry {
+        Product { sku: String },
+        Note(String),
+    }
+}
+
+#[test]
+fn public_fields() {
+    let columns: [&str; catalog::Product::FIELD_COUNT] = catalog::Product::FIELDS;
+    assert_eq!(columns, ["sku", "price"]);
+    assert_eq!(catalog::Draft::FIELDS, ["title"]);
+    assert_eq!(catalog::Draft::FIELD_COUNT, 1);
+    assert_eq!(catalog::Entry::FIELD_COUNT, 2);
+    assert_eq!(
+        catalog::Entry::VARIANT_FIELDS,
+        [("Product", &["sku"][..]), ("Note", &["0"][..])]
+    );
+}
+
+struct Secret;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(parse, derive_all = [Debug])]
+struct Account {
+    #[fieldname(skip)]
+    token: Secret,
+    #[fieldname(skip_mut)]
+    id: u64,
+    login: String,
+    #[fieldname(skip_iter)]
+    rating: u32,
+}
+
+#[derive(FieldnameAccess)]
+enum Event {
+    Login {
+        #[fieldname(skip_mut)]
+        id: u64,
+        #[fieldname(skip)]
+        _token: Secret,
+    },
+    Rate(u64, #[fieldname(skip_iter)] u32),
+}
+
+#[test]
+fn skipped_fields() {
+    let mut account = Account {
+        token: Secret,
+        id: 7,
+        login: String::from("ranni"),
+        rating: 5,
+    };
+    let Secret = account.token;
+
+    assert_eq!(Account::FIELDS, ["id", "login", "rating"]);
+    assert!(account.field("token").is_none());
+    assert!(matches!(account.field("id"), Some(AccountField::U64(7))));
+    assert!(account.field_mut("id").is_none());
+    assert!(account.field_at_mut(0).is_none());
+    assert!(matches!(
+        account.field_at_mut(2),
+        Some(AccountFieldMut::U32(_))
+    ));
+    assert!(account.field_by_name_mut(AccountFieldName::Id).is_none());
+    assert_eq!(
+        account.set_field("id", AccountFieldValue::U64(8)).err(),
+        Some(SetFieldError::ReadOnly("id"))
+    );
+    assert_eq!(
+        account.set_field_str("id", "8"),
+        Err(ParseFieldError::NotParsable("id"))
+    );
+    assert_eq!(account.id, 7);
+    if let Some(AccountFieldMut::U32(rating)) = account.field_mut("rating") {
+        *rating = 4;
+    }
+    assert!(matches!(
+        account.field("rating"),
+        Some(AccountField::U32(4))
+    ));
+    assert_eq!(
+        account
+            .field_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        ["id", "login"]
+    );
+
+    let mut event = Event::Login {
+        id: 1,
+        _token: Secret,
+    };
+    assert_eq!(Event::FIELDS, ["id", "0", "1"]);
+    assert!(event.field_mut("id").is_none());
+    assert!(event.field("_token").is_none());
+    assert_eq!(event.field_iter().count(), 1);
+    event = Event::Rate(2, 3);
+    assert!(event.field_mut("1").is_some());
+    assert_eq!(
+        event.field_iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["0"]
+    );
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(rename_all = "camelCase")]
+struct WireUser {
+    date_of_birth: u64,
+    #[fieldname(rename = "user-name")]
+    user_name: String,
+    r#type: u8,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(rename_all = "SCREAMING-KEBAB-CASE")]
+enum WireEvent {
+    Created { created_at: u64 },
+    Deleted(#[fieldname(rename = "deletedAt")] u64),
+}
+
+#[test]
+fn renamed_fields() {
+    let mut user = WireUser::default();
+    assert_eq!(WireUser::FIELDS, ["dateOfBirth", "user-name", "type"]);
+    assert!(user.field("date_of_birth").is_none());
+    if let Some(WireUserFieldMut::U64(date)) = user.field_mut("dateOfBirth") {
+        *date = 10;
     }
+    assert_eq!(user.date_of_birth, 10);
+    assert!(matches!(user.field("type"), Some(WireUserField::U8(0))));
+    assert_eq!(
+        user.field_iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["dateOfBirth", "user-name", "type"]
+    );
+    assert_eq!(WireUserFieldName::DateOfBirth.as_str(), "dateOfBirth");
+    assert_eq!(
+        "user-name".parse::<WireUserFieldName>(),
+        Ok(WireUserFieldName::UserName)
+    );
+    assert_eq!(WireUserFieldName::Type.to_string(), "type");
+
+    assert_eq!(WireEvent::FIELDS, ["CREATED-AT", "deletedAt"]);
+    let event = WireEvent::Deleted(3);
+    assert!(matches!(
+        event.field("deletedAt"),
+        Some(WireEventField::U64(3))
+    ));
+    let event = WireEvent::Created { created_at: 1 };
+    assert!(event.field("CREATED-AT").is_some());
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(parse)]
+struct Criteria {
+    #[fieldname(alias = "username", alias = "login")]
+    name: String,
+    #[fieldname(rename = "minAge", alias = "min_age")]
+    age: u64,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(into_fields)]
+enum Job {
+    Queued {
+        #[fieldname(alias = "job_id")]
+        id: u64,
+    },
+    Done {
+        #[fieldname(alias = "job_id")]
+        id: u64,
+        code: i32,
+    },
+}
+
+#[test]
+fn aliased_fields() {
+    let mut criteria = Criteria::default();
+    assert_eq!(Criteria::FIELDS, ["name", "minAge"]);
+    assert!(matches!(
+        criteria.field("login"),
+        Some(CriteriaField::String(_))
+    ));
+    if let Some(CriteriaFieldMut::U64(age)) = criteria.field_mut("min_age") {
+        *age = 18;
+    }
+    assert_eq!(criteria.age, 18);
+    criteria.set_field_str("username", "ranni").unwrap();
+    assert_eq!(criteria.name, "ranni");
+    assert!(criteria
+        .set_field("login", CriteriaFieldValue::String(String::from("blaidd")))
+        .is_ok());
+    assert_eq!(criteria.name, "blaidd");
+    assert_eq!(
+        criteria
+            .field_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        ["name", "minAge"]
+    );
+
+    assert_eq!(Criteria::canonical_name("login"), Some("name"));
+    assert_eq!(Criteria::canonical_name("minAge"), Some("minAge"));
+    assert_eq!(Criteria::canonical_name("age"), None);
+    assert!(Criteria::is_deprecated_alias("min_age"));
+    assert!(!Criteria::is_deprecated_alias("minAge"));
+    assert_eq!(
+        "username".parse::<CriteriaFieldName>(),
+        Ok(CriteriaFieldName::Name)
+    );
+    assert!(!TestStruct::is_deprecated_alias("name"));
+
+    let job = Job::Done { id: 3, code: 0 };
+    assert!(matches!(job.field("job_id"), Some(JobField::U64(3))));
+    assert_eq!(Job::canonical_name("job_id"), Some("id"));
+    assert!(matches!(
+        Job::Queued { id: 4 }.into_field("job_id"),
+        Some(JobFieldValue::U64(4))
+    ));
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(lookup = "ascii_case_insensitive", parse)]
+struct Filter {
+    name: String,
+    #[fieldname(alias = "years")]
+    age: u64,
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(lookup = "normalized", rename_all = "camelCase", into_fields)]
+struct NormalizedFilter {
+    user_name: String,
+    date_of_birth: u64,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(lookup = "normalized")]
+enum Lookup {
+    ByName { user_name: String },
+    ById { user_id: u64 },
+}
+
+#[test]
+fn lookup_modes() {
+    let mut filter = Filter::default();
+    assert!(matches!(filter.field("NAME"), Some(FilterField::String(_))));
+    assert!(matches!(filter.field("Years"), Some(FilterField::U64(_))));
+    assert!(filter.field("user_name").is_none());
+    if let Some(FilterFieldMut::U64(age)) = filter.field_mut("AGE") {
+        *age = 18;
+    }
+    assert_eq!(filter.age, 18);
+    filter.set_field_str("Name", "ranni").unwrap();
+    assert_eq!(filter.name, "ranni");
+    assert_eq!(
+        filter.set_field_str("nam", "ranni"),
+        Err(ParseFieldError::UnknownField(String::from("nam")))
+    );
+    assert!(filter.set_field("AgE", FilterFieldValue::U64(20)).is_ok());
+    assert_eq!(filter.age, 20);
+    assert_eq!(Filter::canonical_name("YEARS"), Some("age"));
+    assert!(Filter::is_deprecated_alias("YEARS"));
+    assert!(!Filter::is_deprecated_alias("AGE"));
+    assert!(!Filter::is_deprecated_alias("yearz"));
+    assert_eq!("NaMe".parse::<FilterFieldName>(), Ok(FilterFieldName::Name));
+
+    let filter = NormalizedFilter {
+        user_name: String::from("radahn"),
+        date_of_birth: 0,
+    };
+    assert!(
+        matches!(filter.field("user-name"), Some(NormalizedFilterField::String(name)) if name == "radahn")
+    );
+    assert!(filter.field("USER_NAME").is_some());
+    assert!(filter.field("date_of_birth").is_some());
+    assert!(filter.field("user").is_none());
+    assert!(matches!(
+        filter.into_field("DateOfBirth"),
+        Some(NormalizedFilterFieldValue::U64(0))
+    ));
+
+    let lookup = Lookup::ById { user_id: 4 };
+    assert!(lookup.field("User-Id").is_some());
+    assert!(lookup.field("user-name").is_none());
+    let lookup = Lookup::ByName {
+        user_name: String::from("ranni"),
+    };
+    assert!(lookup.field("UserName").is_some());
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive_all = [Debug])]
+struct Profile {
+    name: String,
+    age: u64,
+    page: u64,
+    #[fieldname(skip_mut)]
+    extra: u8,
+}
+
+#[test]
+fn unknown_field_suggestions() {
+    let mut profile = Profile {
+        name: String::from("ranni"),
+        age: 18,
+        page: 1,
+        extra: 0,
+    };
+    assert!(matches!(
+        profile.try_field("age"),
+        Ok(ProfileField::U64(18))
+    ));
+
+    let err = profile.try_field("agee").unwrap_err();
+    assert_eq!(err.requested, "agee");
+    assert_eq!(err.type_name, "Profile");
+    assert_eq!(err.valid, ["name", "age", "page", "extra"]);
+    assert_eq!(err.suggestions, ["age"]);
+    assert_eq!(
+        err.to_string(),
+        "unknown field \"agee\" on Profile, did you mean \"age\"?"
+    );
+    assert_eq!(
+        profile.try_field("ag").unwrap_err().to_string(),
+        "unknown field \"ag\" on Profile, did you mean \"age\"?"
+    );
+    assert_eq!(
+        profile.try_field("xage").unwrap_err().to_string(),
+        "unknown field \"xage\" on Profile, did you mean \"age\" or \"page\"?"
+    );
+    assert_eq!(profile.try_field("nmae").unwrap_err().suggestions, ["name"]);
+    assert_eq!(profile.try_field("gae").unwrap_err().suggestions, ["age"]);
+    assert_eq!(
+        profile.try_field("surname").unwrap_err().to_string(),
+        "unknown field \"surname\" on Profile"
+    );
+
+    if let Ok(ProfileFieldMut::U64(page)) = profile.try_field_mut("page") {
+        *page = 2;
+    }
+    assert_eq!(profile.page, 2);
+    let err = profile.try_field_mut("extra").unwrap_err();
+    assert_eq!(err.valid, ["name", "age", "page"]);
+    assert!(err.suggestions.is_empty());
+
+    let shape = Shape::Circle { id: 1, radius: 2.0 };
+    let err = shape.try_field("w").err().unwrap();
+    assert_eq!(err.type_name, "Shape::Circle");
+    assert_eq!(err.valid, ["id", "radius"]);
+    assert!(err.suggestions.is_empty());
+    assert_eq!(shape.try_field("ids").err().unwrap().suggestions, ["id"]);
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Cert {
+    path: String,
+    expires: u64,
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Tls {
+    #[fieldname(nested)]
+    cert: Cert,
+    enabled: bool,
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Server {
+    port: u16,
+    #[fieldname(nested, alias = "ssl")]
+    tls: Tls,
+    #[fieldname(nested, skip_mut)]
+    backup: Tls,
+}
+
+#[derive(FieldnameAccess)]
+enum Endpoint {
+    Secure {
+        #[fieldname(nested)]
+        tls: Tls,
+    },
+    Plain {
+        port: u16,
+    },
+}
+
+#[test]
+fn nested_paths() {
+    let mut server = Server::default();
+    assert_eq!(
+        Server::field_paths(),
+        [
+            "port",
+            "tls.cert.path",
+            "tls.cert.expires",
+            "tls.enabled",
+            "backup.cert.path",
+            "backup.cert.expires",
+            "backup.enabled"
+        ]
+    );
+    assert_eq!(Tls::field_paths(), ["cert.path", "cert.expires", "enabled"]);
+
+    assert!(matches!(
+        server.field_path("port"),
+        Ok(ServerFieldPath::Field(ServerField::U16(0)))
+    ));
+    assert!(matches!(
+        server.field_path("tls"),
+        Ok(ServerFieldPath::Field(ServerField::Tls(_)))
+    ));
+    assert!(matches!(
+        server.field_path("tls.enabled"),
+        Ok(ServerFieldPath::Tls(TlsFieldPath::Field(TlsField::Bool(
+            false
+        ))))
+    ));
+    if let Ok(ServerFieldPathMut::Tls(TlsFieldPathMut::Cert(CertFieldPathMut::Field(
+        CertFieldMut::String(path),
+    )))) = server.field_path_mut("ssl.cert.path")
+    {
+        *path = String::from("/etc/cert.pem");
+    }
+    assert_eq!(server.tls.cert.path, "/etc/cert.pem");
+    assert!(server.field_path("backup.cert.expires").is_ok());
+    assert!(server.field_path_mut("backup.cert.expires").is_err());
+    assert_eq!(
+        server.field_path("port.value").err().unwrap().kind,
+        FieldPathErrorKind::NotNavigable
+    );
+    let err = server.field_path("tls.cert.owner").err().unwrap();
+    assert_eq!(err.path, "tls.cert.owner");
+    assert_eq!(err.kind, FieldPathErrorKind::UnknownField);
+    assert_eq!(
+        server.field_path("tls.").err().unwrap().kind,
+        FieldPathErrorKind::UnknownField
+    );
+    assert_eq!(
+        server.field_path("proxy.port").err().unwrap().to_string(),
+        "unknown field `proxy`"
+    );
+
+    assert_eq!(
+        Endpoint::field_paths(),
+        ["tls.cert.path", "tls.cert.expires", "tls.enabled", "port"]
+    );
+    let mut endpoint = Endpoint::Secure {
+        tls: Tls::default(),
+    };
+    if let Ok(EndpointFieldPathMut::Tls(TlsFieldPathMut::Field(TlsFieldMut::Bool(enabled)))) =
+        endpoint.field_path_mut("tls.enabled")
+    {
+        *enabled = true;
+    }
+    assert!(matches!(
+        endpoint.field_path("tls.enabled"),
+        Ok(EndpointFieldPath::Tls(TlsFieldPath::Field(TlsField::Bool(
+            true
+        ))))
+    ));
+    assert!(Endpoint::Plain { port: 80 }
+        .field_path("tls.enabled")
+        .is_err());
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(into_fields)]
+struct Audit {
+    created_at: u64,
+    #[fieldname(skip_iter)]
+    updated_by: String,
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(into_fields)]
+struct Revision {
+    number: u32,
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(into_fields)]
+struct Record<T> {
+    id: u64,
+    #[fieldname(flatten)]
+    audit: Audit,
+    payload: T,
+    #[fieldname(flatten, skip_mut)]
+    revision: Revision,
+}
+
+#[test]
+fn flattened_fields() {
+    let mut record = Record::<bool>::default();
+    assert_eq!(
+        Record::<bool>::FIELDS,
+        ["id", "created_at", "updated_by", "payload", "number"]
+    );
+    assert_eq!(Record::<bool>::FIELD_COUNT, 5);
+    assert_eq!(
+        Record::<bool>::ITER_FIELDS,
+        ["id", "created_at", "payload", "number"]
+    );
+    assert!(matches!(
+        record.field("created_at"),
+        Some(RecordField::Audit(AuditField::U64(0)))
+    ));
+    if let Some(RecordFieldMut::Audit(AuditFieldMut::String(updated_by))) =
+        record.field_mut("updated_by")
+    {
+        *updated_by = String::from("ranni");
+    }
+    assert_eq!(record.audit.updated_by, "ranni");
+    assert!(record.field("audit").is_none());
+    assert!(record.field("number").is_some());
+    assert!(record.field_mut("number").is_none());
+    assert!(matches!(record.field_at(3), Some(RecordField::T(false))));
+    assert!(record.field_at_mut(4).is_none());
+    assert_eq!(
+        Record::<bool>::canonical_name("created_at"),
+        Some("created_at")
+    );
+    assert_eq!(
+        record
+            .field_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        ["id", "created_at", "payload", "number"]
+    );
+    assert_eq!(
+        record.try_field("created").err().unwrap().suggestions,
+        Vec::<&str>::new()
+    );
+    assert_eq!(
+        record.try_field("created_a").err().unwrap().suggestions,
+        ["created_at"]
+    );
+}
+
+#[test]
+fn flattened_fields_owned() {
+    let mut record = Record {
+        id: 1,
+        audit: Audit {
+            created_at: 2,
+            updated_by: String::from("ranni"),
+        },
+        payload: 'x',
+        revision: Revision { number: 3 },
+    };
+    assert_eq!(Record::<char>::field_paths(), Record::<char>::FIELDS);
+    assert!(matches!(
+        record.set_field(
+            "created_at",
+            RecordFieldValue::Audit(AuditFieldValue::U64(5))
+        ),
+        Ok(RecordFieldValue::Audit(AuditFieldValue::U64(2)))
+    ));
+    assert_eq!(record.audit.created_at, 5);
+    assert_eq!(
+        record
+            .set_field("created_at", RecordFieldValue::U64(6))
+            .err(),
+        Some(SetFieldError::MismatchedValue {
+            field: "created_at",
+            expected: "Audit",
+            found: "U64",
+        })
+    );
+    assert_eq!(
+        record
+            .set_field(
+                "number",
+                RecordFieldValue::Revision(RevisionFieldValue::U32(4))
+            )
+            .err(),
+        Some(SetFieldError::ReadOnly("number"))
+    );
+    assert_eq!(
+        record.set_field("audit", RecordFieldValue::U64(6)).err(),
+        Some(SetFieldError::UnknownField(String::from("audit")))
+    );
+
+    let fields = Record {
+        id: 1,
+        audit: Audit::default(),
+        payload: 'x',
+        revision: Revision { number: 3 },
+    }
+    .into_fields()
+    .map(|(name, _)| name)
+    .collect::<Vec<_>>();
+    assert_eq!(fields, Record::<char>::FIELDS);
+    assert!(matches!(
+        record.into_field("updated_by"),
+        Some(RecordFieldValue::Audit(AuditFieldValue::String(updated_by))) if updated_by == "ranni"
+    ));
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Stamp {
+    #[fieldname(alias = "created")]
+    created_at: u64,
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(lookup = "normalized", parse)]
+struct Ticket {
+    title: String,
+    #[fieldname(flatten)]
+    stamp: Stamp,
+}
+
+#[test]
+fn flattened_fields_lookup() {
+    let mut ticket = Ticket::default();
+    if let Some(TicketFieldMut::Stamp(StampFieldMut::U64(created_at))) =
+        ticket.field_mut("createdAt")
+    {
+        *created_at = 7;
+    }
+    assert_eq!(ticket.stamp.created_at, 7);
+    assert!(matches!(
+        ticket.field("Created-At"),
+        Some(TicketField::Stamp(StampField::U64(7)))
+    ));
+    assert!(ticket.field_mut("CREATED").is_some());
+    assert!(ticket.field("createdBy").is_none());
+    assert_eq!(Ticket::canonical_name("Created"), Some("created_at"));
+    assert!(Ticket::is_deprecated_alias("created"));
+    assert!(Ticket::is_deprecated_alias("Created"));
+    assert!(!Ticket::is_deprecated_alias("createdAt"));
+    assert!(Stamp::is_deprecated_alias("created"));
+    assert!("createdAt".parse::<TicketFieldName>().is_err());
+    assert!(ticket
+        .set_field("Created", TicketFieldValue::Stamp(StampFieldValue::U64(9)))
+        .is_ok());
+    assert_eq!(ticket.stamp.created_at, 9);
+    ticket.set_field_str("Title", "lost").unwrap();
+    assert_eq!(ticket.title, "lost");
+    assert_eq!(
+        ticket.set_field_str("createdAt", "8"),
+        Err(ParseFieldError::NotParsable("created_at"))
+    );
+    assert_eq!(
+        ticket.set_field_str("updated_at", "8"),
+        Err(ParseFieldError::UnknownField(String::from("updated_at")))
+    );
+}
+
+#[derive(FieldnameAccess, Default)]
+struct LineItem {
+    sku: String,
+    price: f64,
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Order {
+    #[fieldname(nested)]
+    items: Vec<LineItem>,
+    labels: HashMap<String, String>,
+    grid: [[u8; 2]; 2],
+    #[fieldname(nested)]
+    gift: Option<LineItem>,
+    notes: Option<Vec<String>>,
+}
+
+#[test]
+fn indexed_paths() {
+    let mut order = Order {
+        items: vec![LineItem::default(), LineItem::default()],
+        labels: HashMap::from([(String::from("env"), String::from("prod"))]),
+        ..Default::default()
+    };
+    assert_eq!(
+        Order::field_paths(),
+        [
+            "items[].sku",
+            "items[].price",
+            "labels",
+            "grid",
+            "gift.sku",
+            "gift.price",
+            "notes"
+        ]
+    );
+
+    if let Ok(OrderFieldPathMut::Items(LineItemFieldPathMut::Field(LineItemFieldMut::F64(price)))) =
+        order.field_path_mut("items[1].price")
+    {
+        *price = 9.5;
+    }
+    assert_eq!(order.items[1].price, 9.5);
+    assert!(matches!(
+        order.field_path(r#"labels["env"]"#),
+        Ok(OrderFieldPath::Labels(env)) if env == "prod"
+    ));
+    if let Ok(OrderFieldPathMut::Grid(cell)) = order.field_path_mut("grid[1][0]") {
+        *cell = 7;
+    }
+    assert_eq!(order.grid, [[0, 0], [7, 0]]);
+    assert!(matches!(
+        order.field_path("items"),
+        Ok(OrderFieldPath::Field(OrderField::VecLineItem(items))) if items.len() == 2
+    ));
+
+    let err = order.field_path("items[2].price").err().unwrap();
+    assert_eq!(err.path, "items[2]");
+    assert_eq!(
+        err.kind,
+        FieldPathErrorKind::OutOfBounds { index: 2, len: 2 }
+    );
+    assert_eq!(
+        err.to_string(),
+        "index 2 of `items[2]` is out of bounds for length 2"
+    );
+    let err = order.field_path(r#"labels["region"]"#).err().unwrap();
+    assert_eq!(err.path, r#"labels["region"]"#);
+    assert_eq!(
+        err.kind,
+        FieldPathErrorKind::MissingKey(String::from("region"))
+    );
+    let err = order.field_path("gift.price").err().unwrap();
+    assert_eq!(err.path, "gift");
+    assert_eq!(err.kind, FieldPathErrorKind::NoneValue);
+    assert_eq!(err.to_string(), "`gift` is None");
+    assert_eq!(
+        order.field_path("notes[0]").err().unwrap().kind,
+        FieldPathErrorKind::NoneValue
+    );
+    let err = order.field_path("items[0].cost").err().unwrap();
+    assert_eq!(err.path, "items[0].cost");
+    assert_eq!(err.kind, FieldPathErrorKind::UnknownField);
+    assert_eq!(
+        order.field_path("items[0]").err().unwrap().kind,
+        FieldPathErrorKind::IncompletePath
+    );
+    assert_eq!(
+        order.field_path("items[0].price.value").err().unwrap(),
+        FieldPathError {
+            path: String::from("items[0].price"),
+            kind: FieldPathErrorKind::NotNavigable,
+        }
+    );
+    assert_eq!(
+        order.field_path("grid[x]").err().unwrap(),
+        FieldPathError {
+            path: String::from("grid[x]"),
+            kind: FieldPathErrorKind::InvalidSyntax { position: 4 },
+        }
+    );
+    assert_eq!(
+        order.field_path("items[0").err().unwrap().kind,
+        FieldPathErrorKind::InvalidSyntax { position: 5 }
+    );
+
+    order.gift = Some(LineItem::default());
+    order.notes = Some(vec![String::from("fragile")]);
+    assert!(matches!(
+        order.field_path("gift.sku"),
+        Ok(OrderFieldPath::Gift(LineItemFieldPath::Field(
+            LineItemField::String(_)
+        )))
+    ));
+    assert!(matches!(
+        order.field_path("notes[0]"),
+        Ok(OrderFieldPath::Notes(note)) if note == "fragile"
+    ));
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Blob {
+    field: Vec<u8>,
+    other: u8,
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Relay {
+    #[fieldname(nested)]
+    field: LineItem,
+}
+
+#[test]
+fn paths_through_field_named_field() {
+    let mut blob = Blob {
+        field: vec![1, 2],
+        other: 3,
+    };
+    assert_eq!(Blob::field_paths(), ["field", "other"]);
+    assert!(matches!(blob.field_path("field[1]"), Ok(BlobFieldPath::Field_(byte)) if *byte == 2));
+    assert!(matches!(
+        blob.field_path("field"),
+        Ok(BlobFieldPath::Field(BlobField::VecU8(bytes))) if bytes.len() == 2
+    ));
+    if let Ok(BlobFieldPathMut::Field_(byte)) = blob.field_path_mut("field[0]") {
+        *byte = 7;
+    }
+    assert_eq!(blob.field, [7, 2]);
+
+    let relay = Relay::default();
+    assert_eq!(Relay::field_paths(), ["field.sku", "field.price"]);
+    assert!(matches!(
+        relay.field_path("field.price"),
+        Ok(RelayFieldPath::Field_(LineItemFieldPath::Field(
+            LineItemField::F64(_)
+        )))
+    ));
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Post {
+    title: String,
+    body: String,
+    words: u32,
+    #[fieldname(skip_mut)]
+    id: u64,
+    #[fieldname(skip_iter)]
+    slug: String,
+}
+
+#[test]
+fn mutable_field_iter() {
+    let mut post = Post {
+        title: String::from("  Hello "),
+        body: String::from("world  "),
+        words: 2,
+        id: 7,
+        slug: String::from(" hello "),
+    };
+    for (_, field) in post.field_iter_mut() {
+        match field {
+            PostFieldMut::String(value) => *value = value.trim().to_string(),
+            PostFieldMut::U32(value) => *value = 0,
+        }
+    }
+    assert_eq!(
+        (post.title.as_str(), post.body.as_str(), post.words),
+        ("Hello", "world", 0)
+    );
+    assert_eq!((post.id, post.slug.as_str()), (7, " hello "));
+    assert_eq!(
+        post.field_iter_mut()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        ["title", "body", "words"]
+    );
+
+    let mut record = Record::<bool>::default();
+    assert_eq!(
+        record
+            .field_iter_mut()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        ["id", "created_at", "payload"]
+    );
+    for (_, field) in record.field_iter_mut() {
+        match field {
+            RecordFieldMut::U64(value) | RecordFieldMut::Audit(AuditFieldMut::U64(value)) => {
+                *value = 1
+            }
+            RecordFieldMut::T(value) => *value = true,
+            _ => {}
+        }
+    }
+    assert_eq!((record.id, record.audit.created_at), (1, 1));
+    assert!(record.payload);
+
+    let mut event = Event::Rate(1, 2);
+    for (_, field) in event.field_iter_mut() {
+        if let EventFieldMut::U64(value) = field {
+            *value += 10;
+        }
+    }
+    assert!(matches!(event, Event::Rate(11, 2)));
+    assert_eq!(
+        Event::Login {
+            id: 1,
+            _token: Secret
+        }
+        .field_iter_mut()
+        .count(),
+        0
+    );
+}
+
+#[test]
+fn field_iter_by_position() {
+    let post = Post::default();
+    let mut iter = post.field_iter();
+    assert_eq!(iter.len(), 4);
+    assert!(matches!(iter.next_back(), Some(("id", PostField::U64(0)))));
+    assert_eq!(
+        iter.clone().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["title", "body", "words"]
+    );
+    assert_eq!(
+        iter.clone().rev().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["words", "body", "title"]
+    );
+    assert!(matches!(iter.nth(2), Some(("words", PostField::U32(0)))));
+    assert_eq!(iter.len(), 0);
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+    assert!(post.field_iter().nth(10).is_none());
+
+    let record = Record::<bool>::default();
+    assert_eq!(
+        record
+            .field_iter()
+            .rev()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        ["number", "payload", "created_at", "id"]
+    );
+    assert!(matches!(
+        record.field_iter().nth(1),
+        Some(("created_at", RecordField::Audit(AuditField::U64(0))))
+    ));
+    assert_eq!(Event::Rate(1, 2).field_iter().len(), 1);
+}
+
+mod ids {
+    pub mod user {
+        #[derive(Debug, Default, PartialEq)]
+        pub struct Id(pub u64);
+    }
+
+    pub mod order {
+        #[derive(Debug, Default, PartialEq)]
+        pub struct Id(pub u64);
+    }
+}
+
+#[derive(FieldnameAccess)]
+struct Shapes {
+    label: &'static str,
+    digest: [u8; 4],
+    nothing: (),
+    pair: (u8, bool),
+    bytes: &'static [u8],
+    raw: *const u8,
+    check: fn(u8) -> bool,
+    shown: Box<dyn Display>,
+    counts: HashMap<String, u8>,
+    user: ids::user::Id,
+    order: ids::order::Id,
+    owner: Option<ids::user::Id>,
+}
+
+#[test]
+fn structural_variant_names() {
+    let mut shapes = Shapes {
+        label: "shapes",
+        digest: [1, 2, 3, 4],
+        nothing: (),
+        pair: (1, true),
+        bytes: b"abc",
+        raw: std::ptr::null(),
+        check: |value| value > 1,
+        shown: Box::new(5),
+        counts: HashMap::new(),
+        user: ids::user::Id(1),
+        order: ids::order::Id(2),
+        owner: None,
+    };
+    assert!(matches!(
+        shapes.field("label"),
+        Some(ShapesField::RefStr(&"shapes"))
+    ));
+    assert!(matches!(
+        shapes.field("digest"),
+        Some(ShapesField::Array4U8([1, 2, 3, 4]))
+    ));
+    assert!(matches!(
+        shapes.field("nothing"),
+        Some(ShapesField::Unit(()))
+    ));
+    assert!(matches!(
+        shapes.field("pair"),
+        Some(ShapesField::TupleU8Bool((1, true)))
+    ));
+    assert!(matches!(
+        shapes.field("bytes"),
+        Some(ShapesField::RefSliceU8(bytes)) if *bytes == b"abc"
+    ));
+    assert!(matches!(shapes.field("raw"), Some(ShapesField::ConstPtrU8(raw)) if raw.is_null()));
+    assert!(matches!(shapes.field("check"), Some(ShapesField::FnU8ToBool(check)) if check(2)));
+    assert!(matches!(
+        shapes.field("shown"),
+        Some(ShapesField::BoxDynDisplay(shown)) if shown.to_string() == "5"
+    ));
+    assert!(matches!(
+        shapes.field("counts"),
+        Some(ShapesField::HashMapStringU8(counts)) if counts.is_empty()
+    ));
+    assert!(matches!(
+        shapes.field("user"),
+        Some(ShapesField::UserId(ids::user::Id(1)))
+    ));
+    if let Some(ShapesFieldMut::OrderId(order)) = shapes.field_mut("order") {
+        order.0 = 3;
+    }
+    assert_eq!(shapes.order, ids::order::Id(3));
+    assert!(matches!(
+        shapes.field("owner"),
+        Some(ShapesField::OptionId(None))
+    ));
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(variants = "per_field", parse)]
+struct Member {
+    name: String,
+    email: String,
+    #[fieldname = "Years"]
+    age: u64,
+    #[fieldname(flatten)]
+    audit: Audit,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(variants = "per_field")]
+enum Contact {
+    Email { address: String },
+    Phone { code: u16, number: String },
+}
+
+#[test]
+fn per_field_variants() {
+    let mut member = Member {
+        name: String::from("melina"),
+        email: String::from("melina@roundtable.hold"),
+        ..Default::default()
+    };
+    let describe = |field: MemberField| match field {
+        MemberField::Name(name) => format!("name {}", name),
+        MemberField::Email(email) => format!("email {}", email),
+        MemberField::Years(age) => format!("age {}", age),
+        MemberField::Audit(_) => String::from("audit"),
+    };
+    assert_eq!(
+        member
+            .field_iter()
+            .map(|(_, field)| describe(field))
+            .collect::<Vec<_>>(),
+        [
+            "name melina",
+            "email melina@roundtable.hold",
+            "age 0",
+            "audit"
+        ]
+    );
+    if let Some(MemberFieldMut::Email(email)) = member.field_mut("email") {
+        email.clear();
+    }
+    assert!(member.email.is_empty());
+    member.set_field_str("age", "30").unwrap();
+    assert!(matches!(member.field("age"), Some(MemberField::Years(30))));
+    assert_eq!(MemberFieldValue::Name(String::new()).variant_name(), "Name");
+    assert!(matches!(
+        member.field("created_at"),
+        Some(MemberField::Audit(AuditField::U64(0)))
+    ));
+
+    let phone = Contact::Phone {
+        code: 44,
+        number: String::from("0118"),
+    };
+    assert!(matches!(phone.field("code"), Some(ContactField::Code(44))));
+    assert!(matches!(
+        phone.field("number"),
+        Some(ContactField::Number(number)) if number == "0118"
+    ));
+    assert!(phone.field("address").is_none());
+    let email = Contact::Email {
+        address: String::from("hewg@roundtable.hold"),
+    };
+    assert!(matches!(
+        email.field("address"),
+        Some(ContactField::Address(_))
+    ));
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(markers)]
+struct HTTPRequest {
+    retries: u64,
+    timeout: u64,
+    #[fieldname(skip_mut)]
+    sent: u64,
+    #[fieldname(rename = "urlPath")]
+    url_path: String,
+}
+
+mod vault {
+    use fieldname_access::{FieldnameAccess, FieldnameAccessExt};
+
+    pub(crate) struct Secret(u64);
+
+    #[derive(FieldnameAccess)]
+    #[fieldname_enum(markers)]
+    pub struct Vault {
+        secret: Secret,
+        pub label: String,
+    }
+
+    impl Vault {
+        pub fn new(label: &str) -> Self {
+            Self {
+                secret: Secret(42),
+                label: String::from(label),
+            }
+        }
+
+        pub fn secret(&self) -> u64 {
+            self.get::<vault_fields::Secret>().0
+        }
+    }
+}
+
+/// Module named like generated markers module, which is generated only with `markers`
+#[allow(dead_code)]
+mod ledger_fields {}
+
+#[derive(FieldnameAccess)]
+struct Ledger {
+    total: u64,
+}
+
+fn total<T, F: Field<T, Value = u64>>(items: &[T]) -> u64 {
+    items.iter().map(|item| *F::get(item)).sum()
+}
+
+fn reset<T, F: FieldMut<T, Value = u64>>(item: &mut T) {
+    *F::get_mut(item) = 0;
+}
+
+#[test]
+fn typed_field_getters() {
+    let mut request = HTTPRequest {
+        retries: 3,
+        sent: 2,
+        ..Default::default()
+    };
+    assert_eq!(*request.get::<http_request_fields::Retries>(), 3);
+    *request.get_mut::<http_request_fields::UrlPath>() = String::from("/users");
+    assert_eq!(request.url_path, "/users");
+    assert_eq!(
+        <http_request_fields::UrlPath as Field<HTTPRequest>>::NAME,
+        "urlPath"
+    );
+
+    let requests = [
+        request,
+        HTTPRequest {
+            retries: 1,
+            sent: 1,
+            ..Default::default()
+        },
+    ];
+    assert_eq!(total::<_, http_request_fields::Retries>(&requests), 4);
+    assert_eq!(total::<_, http_request_fields::Sent>(&requests), 3);
+
+    let mut request = HTTPRequest {
+        timeout: 30,
+        ..Default::default()
+    };
+    reset::<_, http_request_fields::Timeout>(&mut request);
+    assert_eq!(request.timeout, 0);
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(markers = "cache_markers")]
+struct Cache {
+    hits: u64,
+    entries: HashMap<String, String>,
+}
+
+impl Cache {
+    fn get(&self, key: &str) -> Option<&String> {
+        self.entries.get(key)
+    }
+}
+
+#[test]
+fn typed_field_getters_with_own_get() {
+    let mut cache = Cache::default();
+    cache
+        .entries
+        .insert(String::from("tarnished"), String::from("maiden"));
+    assert_eq!(cache.get("tarnished").unwrap(), "maiden");
+    *cache.get_mut::<cache_markers::Hits>() += 1;
+    assert_eq!(*FieldnameAccessExt::get::<cache_markers::Hits>(&cache), 1);
+}
+
+#[test]
+fn typed_field_getters_visibility() {
+    let vault = vault::Vault::new("crystal");
+    assert_eq!(vault.secret(), 42);
+    assert_eq!(vault.get::<vault::vault_fields::Label>(), "crystal");
+    assert!(Ledger { total: 3 }.field("total").is_some());
+}
+
+fn present_fields<T: FieldnameAccess>(item: &T) -> Vec<&'static str> {
+    T::FIELDS
+        .iter()
+        .copied()
+        .filter(|name| item.field(name).is_some())
+        .collect()
+}
+
+fn first_field_mut<T: FieldnameAccess>(item: &mut T) -> Option<T::FieldMut<'_>> {
+    let name = T::FIELDS.first()?;
+    item.field_mut(name)
+}
+
+#[test]
+fn generic_over_trait() {
+    let mut post = Post::default();
+    assert_eq!(
+        present_fields(&post),
+        ["title", "body", "words", "id", "slug"]
+    );
+    if let Some(PostFieldMut::String(title)) = first_field_mut(&mut post) {
+        title.push_str("Hello");
+    }
+    assert_eq!(post.title, "Hello");
+    assert_eq!(present_fields(&Lookup::ById { user_id: 4 }), ["user_id"]);
+    let names = |item: &Member| {
+        FieldnameAccess::field_iter(item)
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        names(&Member::default()),
+        ["name", "email", "age", "created_at"]
+    );
+}
+
+#[derive(FieldnameAccess, Default)]
+#[fieldname_enum(derive_mut = [Debug])]
+struct Snapshot {
+    #[fieldname(skip_mut)]
+    taken_at: u64,
+    #[fieldname(skip_mut)]
+    label: String,
+}
+
+#[test]
+fn read_only_struct() {
+    let mut snapshot = Snapshot {
+        taken_at: 7,
+        ..Default::default()
+    };
+    assert!(matches!(
+        snapshot.field("taken_at"),
+        Some(SnapshotField::U64(7))
+    ));
+    assert!(snapshot.field_mut("taken_at").is_none());
+    assert!(snapshot.field_at_mut(1).is_none());
+    assert!(snapshot
+        .field_by_name_mut(SnapshotFieldName::Label)
+        .is_none());
+    assert_eq!(snapshot.field_iter_mut().count(), 0);
+    assert!(snapshot.field_path_mut("label").is_err());
+    assert!(matches!(
+        snapshot.set_field("label", SnapshotFieldValue::String(String::new())),
+        Err(SetFieldError::ReadOnly("label"))
+    ));
 }
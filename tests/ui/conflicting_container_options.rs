@@ -0,0 +1,15 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive = [Debug], derive_all = [Debug])]
+struct User {
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive_all = [Debug], derive_mut = [Debug])]
+struct Person {
+    name: String,
+}
+
+fn main() {}
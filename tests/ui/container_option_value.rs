@@ -0,0 +1,45 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(name)]
+struct Person {
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive_all = [Debug<u8>])]
+struct Animal {
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum = "Thing"]
+struct Thing {
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(fields_vis = "public")]
+struct Row {
+    id: u64,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(variants = "per_name")]
+struct Column {
+    id: u64,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(markers = "table fields")]
+struct Table {
+    id: u64,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(markers)]
+enum Cell {
+    Text { value: String },
+}
+
+fn main() {}
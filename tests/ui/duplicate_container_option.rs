@@ -0,0 +1,16 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(name = "UserValue", name = "UserValue")]
+struct User {
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive = [Debug])]
+#[fieldname_enum(derive = [Clone])]
+struct Person {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct Point(#[fieldname(name = "x_pos")] f64, #[fieldname(name = "x-pos")] f64);
+
+fn main() {}
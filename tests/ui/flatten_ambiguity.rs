@@ -0,0 +1,50 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess, Default)]
+struct Audit {
+    created_at: u64,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(lookup = "normalized")]
+struct Ambiguous {
+    #[fieldname(rename = "createdAt")]
+    created: u64,
+    #[fieldname(flatten)]
+    audit: Audit,
+}
+
+#[derive(FieldnameAccess)]
+struct AliasClash {
+    #[fieldname(alias = "created_at")]
+    created: u64,
+    #[fieldname(flatten)]
+    audit: Audit,
+}
+
+#[derive(FieldnameAccess)]
+struct Twice {
+    id: u64,
+    #[fieldname(flatten)]
+    audit: Audit,
+    #[fieldname(flatten)]
+    #[fieldname = "Backup"]
+    backup: Audit,
+}
+
+#[derive(FieldnameAccess, Default)]
+struct Stamp {
+    created_at: u64,
+    #[fieldname(rename = "createdAt")]
+    created: u64,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(lookup = "normalized")]
+struct Mixed {
+    id: u64,
+    #[fieldname(flatten)]
+    stamp: Stamp,
+}
+
+fn main() {}
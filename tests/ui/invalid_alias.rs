@@ -0,0 +1,28 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct Person {
+    #[fieldname(alias = "login", alias = "login")]
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+struct Animal {
+    #[fieldname(alias = "age")]
+    name: String,
+    age: u8,
+}
+
+#[derive(FieldnameAccess)]
+enum Shape {
+    Circle {
+        #[fieldname(alias = "size")]
+        radius: f64,
+    },
+    Square {
+        #[fieldname(alias = "size")]
+        side: f64,
+    },
+}
+
+fn main() {}
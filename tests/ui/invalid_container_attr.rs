@@ -0,0 +1,15 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(name = 42)]
+struct User {
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derive = Debug)]
+struct Person {
+    name: String,
+}
+
+fn main() {}
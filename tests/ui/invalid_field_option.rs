@@ -0,0 +1,21 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct User {
+    #[fieldname(name = "username")]
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+struct Rgb(#[fieldname(nmae = "r")] u8, u8, u8);
+
+#[derive(FieldnameAccess)]
+struct Rgba(#[fieldname(name = "1")] u8, u8);
+
+#[derive(FieldnameAccess)]
+struct Point(#[fieldname(name = "x", name = "y")] f64);
+
+#[derive(FieldnameAccess)]
+struct Age(#[fieldname] u8);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct User {
+    #[fieldname = "Not A Variant"]
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+struct Person {
+    #[fieldname = 42]
+    name: String,
+}
+
+fn main() {}
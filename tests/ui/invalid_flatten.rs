@@ -0,0 +1,49 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess, Default)]
+struct Audit {
+    created_at: u64,
+}
+
+#[derive(FieldnameAccess)]
+struct Record {
+    created_at: u64,
+    #[fieldname(flatten)]
+    audit: Audit,
+}
+
+#[derive(FieldnameAccess)]
+enum Event {
+    Created {
+        #[fieldname(flatten)]
+        audit: Audit,
+    },
+}
+
+#[derive(FieldnameAccess)]
+struct Renamed {
+    id: u64,
+    #[fieldname(flatten, rename = "meta")]
+    audit: Audit,
+}
+
+#[derive(FieldnameAccess)]
+struct Generic<T> {
+    id: T,
+    #[fieldname(flatten)]
+    audit: Wrapper<T>,
+}
+
+#[derive(FieldnameAccess)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[derive(FieldnameAccess)]
+struct SameVariant {
+    log: Audit,
+    #[fieldname(flatten)]
+    audit: Audit,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(lookup = "case_insensitive")]
+struct Person {
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(lookup = "normalized")]
+struct User {
+    user_name: String,
+    #[fieldname(rename = "userName")]
+    login: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(lookup = "ascii_case_insensitive")]
+struct Animal {
+    name: String,
+    #[fieldname(alias = "NAME")]
+    nickname: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess, Default)]
+struct Tls {
+    enabled: bool,
+}
+
+#[derive(FieldnameAccess)]
+struct Server {
+    #[fieldname(nested)]
+    tls: &'static Tls,
+}
+
+fn main() {}
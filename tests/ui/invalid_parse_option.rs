@@ -0,0 +1,28 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct User {
+    #[fieldname(no_parse)]
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+struct Person {
+    #[fieldname(parse_with = "str::parse")]
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(parse)]
+struct Animal {
+    #[fieldname(no_parse, parse_with = "str::parse")]
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(parse = true)]
+struct Plant {
+    name: String,
+}
+
+fn main() {}
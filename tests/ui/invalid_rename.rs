@@ -0,0 +1,19 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(rename_all = "camelcase")]
+struct Person {
+    first_name: String,
+}
+
+#[derive(FieldnameAccess)]
+struct Rgb(#[fieldname(name = "r", rename = "red")] u8, u8, u8);
+
+#[derive(FieldnameAccess)]
+struct Animal {
+    #[fieldname(rename = "name")]
+    nickname: String,
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct Person {
+    #[fieldname(skip, skip_iter)]
+    name: String,
+    age: u8,
+}
+
+#[derive(FieldnameAccess)]
+struct Animal {
+    #[fieldname(skip)]
+    #[fieldname = "Name"]
+    name: String,
+    age: u8,
+}
+
+#[derive(FieldnameAccess)]
+struct Hidden {
+    #[fieldname(skip)]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use fieldname_access::FieldnameAccess;
+
+struct Info;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(parse)]
+struct User {
+    name: String,
+    info: Info,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+union Number {
+    int: i64,
+    float: f64,
+}
+
+fn main() {}
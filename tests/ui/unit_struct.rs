@@ -0,0 +1,6 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct Marker;
+
+fn main() {}
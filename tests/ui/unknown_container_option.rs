@@ -0,0 +1,15 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(derives = [Debug])]
+struct User {
+    name: String,
+}
+
+#[derive(FieldnameAccess)]
+#[fieldname_enum(nmae = "Person")]
+struct Person {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use fieldname_access::FieldnameAccess;
+
+macro_rules! unit {
+    () => {
+        ()
+    };
+}
+
+#[derive(FieldnameAccess)]
+struct Nothing {
+    name: String,
+    nothing: unit!(),
+}
+
+fn main() {}
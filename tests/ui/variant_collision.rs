@@ -0,0 +1,35 @@
+use fieldname_access::FieldnameAccess;
+
+#[derive(FieldnameAccess)]
+struct Explicit {
+    #[fieldname = "Number"]
+    small: u8,
+    #[fieldname = "Number"]
+    large: u64,
+}
+
+#[derive(FieldnameAccess)]
+struct Generated {
+    count: u8,
+    #[fieldname = "U8"]
+    total: u16,
+}
+
+#[derive(FieldnameAccess)]
+enum Measure {
+    Length { value: f64 },
+    Weight {
+        #[fieldname = "F64"]
+        value: f32,
+    },
+}
+
+#[derive(FieldnameAccess)]
+struct Shared {
+    #[fieldname = "Text"]
+    name: String,
+    #[fieldname = "Text"]
+    title: String,
+}
+
+fn main() {}